@@ -0,0 +1,229 @@
+use crate::{
+    converter::ConverterOutput,
+    elements::changelog::{Commit, Release, COMMIT_TYPES},
+    utils::paths,
+};
+use anyhow::Error;
+use handlebars::Handlebars;
+use serde_json::{json, Value};
+
+use std::fs;
+
+#[derive(Debug)]
+pub struct ChangelogAssembler {
+    converted_config: ConverterOutput,
+    handlebars: Handlebars<'static>,
+}
+
+impl ChangelogAssembler {
+    pub fn new(converted_config: ConverterOutput) -> Self {
+        ChangelogAssembler {
+            converted_config,
+            handlebars: Handlebars::new(),
+        }
+    }
+
+    /// Writes the changelog of the project, when the file already exists only the releases missing from it are
+    /// added and the unreleased changes are replaced, the rest is left as it is
+    pub fn assemble(&mut self, output_path: &str) -> Result<(), Error> {
+        let releases = match self.converted_config.releases.clone() {
+            Some(releases) => releases,
+            None => return Ok(()),
+        };
+
+        let (preamble, sections) = match fs::read_to_string(output_path) {
+            Ok(existing) => ChangelogAssembler::split(&existing),
+            Err(_) => (
+                paths::read_util_file_contents(paths::UtilityPath::HeaderChangelog),
+                vec![],
+            ),
+        };
+
+        // the sections of the released versions, with their key, the missing ones are inserted before the first
+        // older version so that a release tagged late doesn't end up on top
+        let mut released: Vec<(String, String)> = sections
+            .into_iter()
+            .filter(|(key, _)| key != "unreleased")
+            .collect();
+
+        let mut changelog: Vec<String> = vec![preamble.trim_end().to_string()];
+
+        for release in &releases {
+            let version = match &release.version {
+                Some(version) => ChangelogAssembler::key(version),
+                None => {
+                    changelog.push(self.assemble_release(release)?);
+                    continue;
+                }
+            };
+
+            if released.iter().any(|(key, _)| *key == version) {
+                continue;
+            }
+
+            let position = released
+                .iter()
+                .position(|(key, _)| {
+                    ChangelogAssembler::order(key) < ChangelogAssembler::order(&version)
+                })
+                .unwrap_or(released.len());
+
+            released.insert(position, (version, self.assemble_release(release)?));
+        }
+
+        changelog.extend(released.into_iter().map(|(_, section)| section));
+
+        let changelog = changelog
+            .iter()
+            .map(|section| section.trim_end())
+            .collect::<Vec<&str>>()
+            .join("\n\n");
+
+        fs::write(output_path, format!("{}\n", changelog))?;
+
+        Ok(())
+    }
+
+    /// Returns the section of the changelog listing the changes of the given release
+    fn assemble_release(&mut self, release: &Release) -> Result<String, Error> {
+        let release_tpl = paths::read_util_file_contents(paths::UtilityPath::ReleaseChangelog);
+        self.handlebars
+            .register_template_string("release_tpl", release_tpl)?;
+
+        let repository = self.converted_config.repository.as_ref();
+
+        let entry = |commit: &Commit, text: &str| -> Value {
+            json!({
+                "scope": commit.scope,
+                "text": text,
+                "short_hash": commit.short_hash(),
+                "url": repository.and_then(|r| r.commit_url(&commit.hash)),
+            })
+        };
+
+        let breaking: Vec<Value> = release
+            .commits
+            .iter()
+            .filter_map(|c| Some(entry(c, c.breaking.as_ref()?)))
+            .collect();
+
+        let groups: Vec<Value> = COMMIT_TYPES
+            .iter()
+            .filter_map(|(kind, title)| {
+                let mut commits: Vec<_> =
+                    release.commits.iter().filter(|c| c.kind == *kind).collect();
+
+                if commits.is_empty() {
+                    return None;
+                }
+
+                // the commits without a scope come first, the order of the history is kept within a scope
+                commits.sort_by(|a, b| a.scope.cmp(&b.scope));
+
+                let commits: Vec<Value> = commits.iter().map(|c| entry(c, &c.subject)).collect();
+
+                Some(json!({ "title": title, "commits": commits }))
+            })
+            .collect();
+
+        // the unreleased changes are compared to the head of the history
+        let url = release.previous.as_ref().and_then(|previous| {
+            repository
+                .and_then(|r| r.compare_url(previous, release.version.as_deref().unwrap_or("HEAD")))
+        });
+
+        Ok(self.handlebars.render(
+            "release_tpl",
+            &json!({
+                "title": release.title(),
+                "url": url,
+                "date": release.date,
+                "breaking": breaking,
+                "groups": groups,
+            }),
+        )?)
+    }
+
+    /// Returns the key identifying a release in a changelog, a tag and a heading may differ in the `v` prefix
+    fn key(version: &str) -> String {
+        version.to_lowercase().trim_start_matches('v').to_string()
+    }
+
+    /// Returns the sort key of the version of a release, its numbers then a pre-release after the release of the
+    /// same version, e.g. `1.2.0-rc.1` comes before `1.2.0` and `1.2.0` before `1.10.0`
+    fn order(version: &str) -> (Vec<u64>, bool, String) {
+        let (core, pre_release) = match version.split_once('-') {
+            Some((core, pre_release)) => (core, Some(pre_release)),
+            None => (version, None),
+        };
+
+        (
+            core.split(|c: char| !c.is_ascii_digit())
+                .filter_map(|n| n.parse::<u64>().ok())
+                .collect(),
+            pre_release.is_none(),
+            pre_release.unwrap_or_default().to_string(),
+        )
+    }
+
+    /// Splits an existing changelog in the text preceding the releases and the sections of the releases, each
+    /// section comes with the key of the version in its heading, e.g. `## [v1.2.0](...) (2023-05-01)`
+    fn split(changelog: &str) -> (String, Vec<(String, String)>) {
+        let mut preamble = String::new();
+        let mut sections: Vec<(String, String)> = vec![];
+
+        for line in changelog.lines() {
+            if let Some(heading) = line.strip_prefix("## ") {
+                let version = heading
+                    .trim()
+                    .trim_start_matches('[')
+                    .split(|c: char| c == ']' || c == '(' || c.is_whitespace())
+                    .next()
+                    .unwrap_or("");
+
+                sections.push((ChangelogAssembler::key(version), String::new()));
+            }
+
+            let target = match sections.last_mut() {
+                Some((_, section)) => section,
+                None => &mut preamble,
+            };
+            target.push_str(line);
+            target.push('\n');
+        }
+
+        (preamble, sections)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn orders_the_pre_releases_before_their_release() {
+        let order = ChangelogAssembler::order;
+
+        assert!(order("1.2.0-rc.1") < order("1.2.0"));
+        assert!(order("1.2.0") < order("1.10.0"));
+        assert!(order("1.2.0-rc.1") < order("1.2.0-rc.2"));
+    }
+
+    #[test]
+    fn splits_the_changelog_in_its_releases() {
+        let changelog = "# Changelog\n\nAll notable changes.\n\n## [Unreleased]\n\n- wip\n\n## [v1.2.0](https://example.org/compare/v1.1.0...v1.2.0) (2023-05-01)\n\n- feat\n\n## 1.1.0\n";
+
+        let (preamble, sections) = ChangelogAssembler::split(changelog);
+
+        assert_eq!(preamble, "# Changelog\n\nAll notable changes.\n\n");
+        assert_eq!(
+            sections
+                .iter()
+                .map(|(key, _)| key.as_str())
+                .collect::<Vec<_>>(),
+            vec!["unreleased", "1.2.0", "1.1.0"]
+        );
+        assert_eq!(sections[0].1, "## [Unreleased]\n\n- wip\n\n");
+        assert!(sections[1].1.ends_with("- feat\n\n"));
+    }
+}
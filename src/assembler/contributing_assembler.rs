@@ -1,15 +1,19 @@
-use crate::utils::paths;
+use crate::{assembler::assemble_tests, converter::ConverterOutput, utils::paths};
 use anyhow::Error;
+use handlebars::Handlebars;
+use serde_json::json;
 
 use std::fs::File;
 use std::io::Write;
 
 #[derive(Debug)]
-pub struct ContributingAssembler {}
+pub struct ContributingAssembler {
+    converted_config: ConverterOutput,
+}
 
 impl ContributingAssembler {
-    pub fn new() -> Self {
-        ContributingAssembler {}
+    pub fn new(converted_config: ConverterOutput) -> Self {
+        ContributingAssembler { converted_config }
     }
 
     pub fn assemble(&mut self, output_path: &str) -> Result<(), Error> {
@@ -22,7 +26,22 @@ impl ContributingAssembler {
 
         let contributing_tpl = paths::read_util_file_contents(paths::UtilityPath::BodyContributing);
 
-        contributing_file.write_all(contributing_tpl.as_bytes())?;
+        let mut handlebars = Handlebars::new();
+        handlebars.register_template_string("contributing_tpl", contributing_tpl)?;
+
+        let contributing = handlebars.render(
+            "contributing_tpl",
+            &json!({
+                "tests": assemble_tests(&self.converted_config.test_commands),
+                "issues_url": self
+                    .converted_config
+                    .repository
+                    .as_ref()
+                    .and_then(|r| r.issues_url()),
+            }),
+        )?;
+
+        contributing_file.write_all(contributing.as_bytes())?;
 
         Ok(())
     }
@@ -1,33 +1,100 @@
-use crate::{converter::ConverterOutput, utils::outputs};
+use crate::{
+    config::Config,
+    converter::ConverterOutput,
+    dialoguer,
+    elements::script::Script,
+    scanner::{scan_dependencies, scan_techs, scan_test_commands},
+    utils::{outputs, paths},
+};
 use anyhow::Error;
+use handlebars::Handlebars;
+use itertools::Itertools;
+use serde_json::json;
+use std::path::Path;
 
+pub mod changelog_assembler;
 pub mod contributing_assembler;
 pub mod readme_assembler;
 
+use changelog_assembler::ChangelogAssembler;
 use contributing_assembler::ContributingAssembler;
 use readme_assembler::ReadmeAssembler;
 
 #[derive(Debug)]
 pub struct Assembler {
     converted_config: ConverterOutput,
+    config: Config,
 }
 
 impl Assembler {
-    pub fn new(converted_config: ConverterOutput) -> Self {
-        Assembler { converted_config }
+    pub fn new(converted_config: ConverterOutput, config: Config) -> Self {
+        Assembler {
+            converted_config,
+            config,
+        }
     }
 
     pub fn assemble(&mut self, project_location: &str, paths: &Vec<String>) -> Result<(), Error> {
+        // the detected test frameworks complete the test commands found in the manifests
+        let techs: Vec<String> = scan_techs(paths)?
+            .into_iter()
+            .chain(scan_dependencies(
+                self.converted_config
+                    .dependencies
+                    .clone()
+                    .unwrap_or_default(),
+                &self
+                    .converted_config
+                    .locked_packages
+                    .clone()
+                    .unwrap_or_default(),
+            )?)
+            .chain(scan_dependencies(
+                self.converted_config
+                    .dev_dependencies
+                    .clone()
+                    .unwrap_or_default(),
+                &[],
+            )?)
+            .unique()
+            .collect();
+
+        let mut test_commands = self
+            .converted_config
+            .test_commands
+            .clone()
+            .unwrap_or_default();
+        test_commands.extend(scan_test_commands(&techs, &test_commands)?);
+        self.converted_config.test_commands = Some(test_commands);
+
         let output_contributing = &format!("{}/{}", project_location, outputs::CONTRIBUTING);
-        match ContributingAssembler::new().assemble(output_contributing) {
+        match ContributingAssembler::new(self.converted_config.clone())
+            .assemble(output_contributing)
+        {
             Ok(_) => {}
             Err(e) => {
                 return Err(e);
             }
         }
 
+        // the changelog is written on demand only, a failure to write it doesn't prevent the README
+        let output_changelog = &format!("{}/{}", project_location, outputs::CHANGELOG);
+        if self.config.changelog.enabled || Path::new(output_changelog).exists() {
+            if let Err(e) =
+                ChangelogAssembler::new(self.converted_config.clone()).assemble(output_changelog)
+            {
+                dialoguer::error("Error: Failed to write the changelog: {}", &e);
+            }
+        }
+
         let output_readme = &format!("{}/{}", project_location, outputs::README);
-        match ReadmeAssembler::new(self.converted_config.clone()).assemble(output_readme, paths) {
+        match ReadmeAssembler::new(
+            self.converted_config.clone(),
+            self.config.clone(),
+            project_location,
+        )
+        .assemble(output_readme, paths)
+        {
             Ok(_) => {
                 return Ok(());
             }
@@ -37,3 +104,21 @@ impl Assembler {
         }
     }
 }
+
+/// Returns the section explaining how to run the tests of the project, shared by the README and the CONTRIBUTING
+pub fn assemble_tests(test_commands: &Option<Vec<Script>>) -> Option<String> {
+    let tests_tpl = paths::read_util_file_contents(paths::UtilityPath::TestsReadme);
+
+    let test_commands = test_commands.as_ref().filter(|t| !t.is_empty())?;
+
+    let mut handlebars = Handlebars::new();
+    handlebars
+        .register_template_string("tests_tpl", tests_tpl)
+        .unwrap();
+
+    Some(
+        handlebars
+            .render("tests_tpl", &json!({ "test_commands": test_commands }))
+            .unwrap(),
+    )
+}
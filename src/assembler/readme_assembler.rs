@@ -1,34 +1,135 @@
 use crate::{
-    converter::ConverterOutput,
-    elements::{license::SupportedLicense, repository::RepositoryPlatform},
+    assembler::assemble_tests,
+    config::{Config, EmailPrivacy},
+    converter::{Contributors, ConverterOutput, DependencySource},
+    elements::{
+        cargo::{Target, TargetKind},
+        license::SupportedLicense,
+        repository::RepositoryPlatform,
+        structure::Directory,
+    },
     scanner::{scan_dependencies, scan_techs},
-    utils::{fantasy_description, paths, shields, Alignment, GenMarkdown},
+    utils::{fantasy_description, outputs, paths, shields, Alignment, GenMarkdown, Shield},
 };
 use anyhow::Error;
 
 use handlebars::Handlebars;
-use serde_json::json;
+use itertools::Itertools;
+use serde_json::{json, Value};
+use std::cmp::Reverse;
 use std::fs::File;
 use std::io::Write;
+use std::path::Path;
+
+/// The branch the links, the CI badges and the protection status refer to when the repository doesn't tell it
+const DEFAULT_BRANCH: &str = "main";
 
 #[derive(Debug)]
 pub struct ReadmeAssembler<'a> {
     handlebars: Handlebars<'a>,
     converted_config: ConverterOutput,
+    config: Config,
+    project_location: String,
 }
 
 impl<'a> ReadmeAssembler<'a> {
-    pub fn new(converted_config: ConverterOutput) -> Self {
+    pub fn new(converted_config: ConverterOutput, config: Config, project_location: &str) -> Self {
         ReadmeAssembler {
             handlebars: Handlebars::new(),
             converted_config,
+            config,
+            project_location: project_location.to_string(),
         }
     }
 
+    /// Returns the branch set in the config or else the default branch of the repository
+    fn branch(&self) -> String {
+        self.config
+            .repository
+            .branch
+            .clone()
+            .or(self.converted_config.default_branch.clone())
+            .unwrap_or(DEFAULT_BRANCH.to_string())
+    }
+
+    /// Returns the link to the given file of the project on the default branch of the repository, if the platform
+    /// hosting it is known
+    fn file_url(&self, path: &str) -> Option<String> {
+        let file = Path::new(path)
+            .strip_prefix(&self.project_location)
+            .map(|p| p.to_string_lossy().to_string())
+            .unwrap_or(path.to_string());
+
+        self.converted_config
+            .repository
+            .as_ref()?
+            .blob_url(&self.branch(), &file)
+    }
+
     fn assemble_header(&mut self, to_make_shields: Vec<String>) -> String {
         let header_tpl = paths::read_util_file_contents(paths::UtilityPath::HeaderReadme);
 
-        let shields = shields(to_make_shields, Alignment::Row).unwrap();
+        let tech_shields = shields(to_make_shields, Alignment::Row).unwrap();
+        let mut shields = String::new();
+
+        // the status of the checks protecting the main branch comes first
+        let repository = self.converted_config.repository.clone().unwrap();
+        let branch = self.branch();
+        for workflow in self
+            .converted_config
+            .workflows
+            .iter()
+            .flatten()
+            .filter(|w| w.protects(&branch))
+        {
+            if let Ok(md) = workflow.badge(&repository, &branch) {
+                shields.push_str(&md);
+                shields.push(' ');
+            }
+        }
+
+        shields.push_str(&tech_shields);
+
+        if let Some(primary_language) = self.converted_config.primary_language.as_ref() {
+            let language = self
+                .converted_config
+                .languages
+                .iter()
+                .flatten()
+                .find(|l| &l.name == primary_language);
+
+            if let Some(language) = language {
+                let shield = Shield::new(
+                    "language",
+                    &language.name,
+                    &language.color,
+                    &language.logo,
+                    &format!("Written in {}", language.name),
+                    "#languages",
+                );
+
+                if let Ok(md) = shield.gen_md() {
+                    shields.push_str(&md);
+                    shields.push(' ');
+                }
+            }
+        }
+
+        if let Some(rust_version) = self.converted_config.rust_version.as_ref() {
+            let msrv = Shield::new(
+                "MSRV",
+                rust_version,
+                "orange",
+                "rust",
+                "Minimum supported Rust version",
+                "https://www.rust-lang.org/tools/install",
+            );
+
+            if let Ok(md) = msrv.gen_md() {
+                shields.push_str(&md);
+                shields.push(' ');
+            }
+        }
 
         // if name is none or empty, set it to default "Project Name"
         if self.converted_config.name.is_none()
@@ -42,7 +143,13 @@ impl<'a> ReadmeAssembler<'a> {
             "description": self.converted_config.description.clone(),
             "shields": Some(shields),
             "fantasy_description": Some(fantasy_description()),
-            "link_one": "/CONTRIBUTING.md",
+            "link_one": self
+                .file_url("CONTRIBUTING.md")
+                .unwrap_or("/CONTRIBUTING.md".to_string()),
+            "changelog": Path::new(&self.project_location)
+                .join(outputs::CHANGELOG)
+                .exists()
+                .then(|| self.file_url(outputs::CHANGELOG).unwrap_or(format!("/{}", outputs::CHANGELOG))),
         });
 
         self.handlebars
@@ -52,43 +159,540 @@ impl<'a> ReadmeAssembler<'a> {
         self.handlebars.render("header_tpl", &header).unwrap()
     }
 
-    fn assemble_table_of_contents(&self) -> String {
-        paths::read_util_file_contents(paths::UtilityPath::TocReadme)
+    fn assemble_table_of_contents(&mut self) -> String {
+        let toc_tpl = paths::read_util_file_contents(paths::UtilityPath::TocReadme);
+
+        let has_dependencies = [
+            &self.converted_config.dependencies,
+            &self.converted_config.dev_dependencies,
+            &self.converted_config.build_dependencies,
+        ]
+        .iter()
+        .any(|d| d.as_ref().is_some_and(|d| !d.is_empty()));
+
+        let toc = json!({
+            "docker": self.converted_config.docker.is_some(),
+            "features": self
+                .converted_config
+                .features
+                .as_ref()
+                .is_some_and(|f| !f.is_empty()),
+            "dependencies": has_dependencies,
+            "languages": self.converted_config.languages.is_some(),
+            "structure": self.converted_config.structure.is_some(),
+            "ci": self
+                .converted_config
+                .workflows
+                .as_ref()
+                .is_some_and(|w| !w.is_empty()),
+            "tests": self
+                .converted_config
+                .test_commands
+                .as_ref()
+                .is_some_and(|t| !t.is_empty()),
+        });
+
+        self.handlebars
+            .register_template_string("toc_tpl", toc_tpl)
+            .unwrap();
+
+        self.handlebars.render("toc_tpl", &toc).unwrap()
+    }
+
+    /// Returns the annotated tree of the directories of the project
+    fn assemble_structure(&mut self) -> Option<String> {
+        let structure_tpl = paths::read_util_file_contents(paths::UtilityPath::StructureReadme);
+
+        let directories = self.converted_config.structure.as_ref()?;
+
+        // the project folder name is more familiar than the package name in a tree
+        let root = Path::new(&self.project_location)
+            .canonicalize()
+            .ok()
+            .and_then(|p| p.file_name().map(|f| f.to_string_lossy().to_string()))
+            .or(self.converted_config.name.clone())
+            .unwrap_or_default();
+
+        self.handlebars
+            .register_template_string("structure_tpl", structure_tpl)
+            .unwrap();
+
+        Some(
+            self.handlebars
+                .render(
+                    "structure_tpl",
+                    &json!({ "tree": Directory::tree(&root, directories) }),
+                )
+                .unwrap(),
+        )
+    }
+
+    /// Returns the share of each language in the source code as a row of shields
+    fn assemble_languages(&mut self) -> Option<String> {
+        let languages_tpl = paths::read_util_file_contents(paths::UtilityPath::LanguagesReadme);
+
+        let languages = self.converted_config.languages.as_ref()?;
+
+        let shields: String = languages
+            .iter()
+            .filter_map(|language| {
+                Shield::new(
+                    &language.name,
+                    &format!("{}%", language.percentage),
+                    &language.color,
+                    &language.logo,
+                    &format!("{} {}%", language.name, language.percentage),
+                    "#languages",
+                )
+                .gen_md()
+                .ok()
+            })
+            .join(" ");
+
+        self.handlebars
+            .register_template_string("languages_tpl", languages_tpl)
+            .unwrap();
+
+        Some(
+            self.handlebars
+                .render(
+                    "languages_tpl",
+                    &json!({ "shields": shields, "languages": languages }),
+                )
+                .unwrap(),
+        )
     }
 
-    fn assemble_body(&mut self) -> String {
-        let body_tpl = paths::read_util_file_contents(paths::UtilityPath::BodyReadme);
+    /// Returns the list of the CI workflows of the project and whether they protect the main branch
+    fn assemble_ci(&mut self) -> Option<String> {
+        let ci_tpl = paths::read_util_file_contents(paths::UtilityPath::CiReadme);
+
+        let workflows = self
+            .converted_config
+            .workflows
+            .as_ref()
+            .filter(|w| !w.is_empty())?;
+        self.converted_config.repository.as_ref()?;
+        let branch = self.branch();
+
+        let workflows: Vec<Value> = workflows
+            .iter()
+            .map(|workflow| {
+                let file = Path::new(&workflow.file)
+                    .strip_prefix(&self.project_location)
+                    .map(|p| p.to_string_lossy().to_string())
+                    .unwrap_or(workflow.file.clone());
+
+                json!({
+                    "name": workflow.name,
+                    "system": workflow.system.to_string(),
+                    "url": self.file_url(&workflow.file).unwrap_or(file),
+                    "protects": workflow.protects(&branch),
+                })
+            })
+            .collect();
+
+        self.handlebars
+            .register_template_string("ci_tpl", ci_tpl)
+            .unwrap();
+
+        Some(
+            self.handlebars
+                .render(
+                    "ci_tpl",
+                    &json!({ "workflows": workflows, "branch": branch }),
+                )
+                .unwrap(),
+        )
+    }
+
+    /// Returns the "Running with Docker" section, if the project has a Dockerfile or a docker-compose file
+    fn assemble_docker(&mut self) -> Option<String> {
+        let docker_tpl = paths::read_util_file_contents(paths::UtilityPath::DockerReadme);
+
+        let docker = self
+            .converted_config
+            .docker
+            .as_ref()?
+            .relative_to(&self.project_location);
+
+        // docker image names must be lowercase and can't contain spaces
+        let tag = self
+            .converted_config
+            .name
+            .clone()
+            .unwrap_or("app".to_string())
+            .to_lowercase()
+            .replace(' ', "-");
+
+        // the -f flag is needed only when the files are not in the project root with the default name
+        let dockerfile = docker
+            .image
+            .as_ref()
+            .map(|image| image.path.clone())
+            .filter(|path| path != "Dockerfile");
+        let compose_file = docker.compose_file.clone().filter(|path| {
+            ![
+                "docker-compose.yml",
+                "docker-compose.yaml",
+                "compose.yml",
+                "compose.yaml",
+            ]
+            .contains(&path.as_str())
+        });
+
+        let code_list = |values: &Vec<String>| -> String {
+            values.iter().map(|v| format!("`{}`", v)).join(", ")
+        };
+
+        let services: Vec<Value> = docker
+            .services
+            .iter()
+            .map(|service| {
+                json!({
+                    "name": service.name,
+                    "image": service
+                        .image
+                        .as_ref()
+                        .map(|image| format!("`{}`", image))
+                        .unwrap_or("built locally".to_string()),
+                    "ports": code_list(&service.ports),
+                    "required_env": code_list(&service.required_env),
+                    "depends_on": service.depends_on.join(", "),
+                })
+            })
+            .collect();
+
+        let data = json!({
+            "image": docker.image.is_some(),
+            "tag": tag,
+            "dockerfile": dockerfile,
+            "ports": docker.image.as_ref().map(|image| image.ports()).unwrap_or_default(),
+            "compose_file": compose_file,
+            "services": services,
+        });
+
+        self.handlebars
+            .register_template_string("docker_tpl", docker_tpl)
+            .unwrap();
+
+        Some(self.handlebars.render("docker_tpl", &data).unwrap())
+    }
+
+    /// Returns the table of the commands available to run the project, if any task was found
+    fn assemble_usage(&mut self) -> Option<String> {
+        let usage_tpl = paths::read_util_file_contents(paths::UtilityPath::UsageReadme);
+
+        let scripts = self.converted_config.scripts.as_ref()?;
+
+        if scripts.is_empty() {
+            return None;
+        }
+
+        // pipes would break the markdown table
+        let escape = |s: &String| s.replace('|', "\\|");
+
+        let scripts: Vec<Value> = scripts
+            .iter()
+            .map(|script| {
+                json!({
+                    "command": escape(&script.command),
+                    "description": script.description.as_ref().map(escape).unwrap_or_default(),
+                })
+            })
+            .collect();
+
+        self.handlebars
+            .register_template_string("usage_tpl", usage_tpl)
+            .unwrap();
+
+        Some(
+            self.handlebars
+                .render("usage_tpl", &json!({ "scripts": scripts }))
+                .unwrap(),
+        )
+    }
+
+    /// Returns the list of the tools to install before running the project, if any is pinned or required
+    fn assemble_prerequisites(&mut self) -> Option<String> {
+        let prerequisites_tpl =
+            paths::read_util_file_contents(paths::UtilityPath::PrerequisitesReadme);
+
+        let prerequisites = self.converted_config.prerequisites.as_ref()?;
+
+        let prerequisites: Vec<Value> = prerequisites
+            .iter()
+            .map(|prerequisite| {
+                json!({
+                    "name": prerequisite.name(),
+                    "url": prerequisite.install_url(),
+                    "version": prerequisite.version,
+                    "minimum": prerequisite.minimum,
+                    "pin": prerequisite.pin,
+                })
+            })
+            .collect();
+
+        self.handlebars
+            .register_template_string("prerequisites_tpl", prerequisites_tpl)
+            .unwrap();
+
+        Some(
+            self.handlebars
+                .render(
+                    "prerequisites_tpl",
+                    &json!({ "prerequisites": prerequisites }),
+                )
+                .unwrap(),
+        )
+    }
+
+    /// Returns the table of the environment variables documented in the env template of the project, if any
+    fn assemble_env_vars(&mut self) -> Option<String> {
+        let env_tpl = paths::read_util_file_contents(paths::UtilityPath::EnvReadme);
 
-        let license = match self.converted_config.license {
-            Some(ref mut license) if license.name != SupportedLicense::Unknown => {
-                let repository = self.converted_config.repository.as_ref().unwrap();
-                if repository.platform == RepositoryPlatform::Github {
-                    // get the file name
-                    let file_name = license
-                        .path
+        let env_vars = self.converted_config.env_vars.as_ref()?;
+        let source = env_vars.first()?.source.clone();
+
+        // pipes would break the markdown table
+        let escape = |s: &String| s.replace('|', "\\|");
+
+        let env_vars: Vec<Value> = env_vars
+            .iter()
+            .map(|env_var| {
+                json!({
+                    "name": env_var.name,
+                    "default": env_var
+                        .default
                         .as_ref()
-                        .unwrap()
-                        .split('/')
-                        .last()
-                        .unwrap_or(&"")
-                        .split('.')
-                        .next()
-                        .unwrap_or(&"");
-
-                    license.url = Some(format!(
-                        "{}/blob/master/{}",
-                        repository.url.clone(),
-                        file_name
+                        .map(|d| format!("`{}`", escape(d)))
+                        .unwrap_or_default(),
+                    "required": env_var.required(),
+                    "description": env_var.description.as_ref().map(escape).unwrap_or_default(),
+                })
+            })
+            .collect();
+
+        let source = Path::new(&source)
+            .strip_prefix(&self.project_location)
+            .map(|p| p.to_string_lossy().to_string())
+            .unwrap_or(source);
+
+        self.handlebars
+            .register_template_string("env_tpl", env_tpl)
+            .unwrap();
+
+        Some(
+            self.handlebars
+                .render(
+                    "env_tpl",
+                    &json!({ "env_vars": env_vars, "source": source }),
+                )
+                .unwrap(),
+        )
+    }
+
+    /// Returns the table of the Cargo features, if the project declares any
+    fn assemble_features(&mut self) -> Option<String> {
+        let features_tpl = paths::read_util_file_contents(paths::UtilityPath::FeaturesReadme);
+
+        let features = self.converted_config.features.as_ref()?;
+
+        if features.is_empty() {
+            return None;
+        }
+
+        let features: Vec<Value> = features
+            .iter()
+            .map(|feature| {
+                json!({
+                    "name": feature.name,
+                    "default": feature.default,
+                    "enables": feature.enables.iter().map(|e| format!("`{}`", e)).join(", "),
+                })
+            })
+            .collect();
+
+        self.handlebars
+            .register_template_string("features_tpl", features_tpl)
+            .unwrap();
+
+        Some(
+            self.handlebars
+                .render("features_tpl", &json!({ "features": features }))
+                .unwrap(),
+        )
+    }
+
+    /// Returns the table of the dependencies of the project, grouped by kind and sorted by name
+    fn assemble_dependencies(&mut self) -> Option<String> {
+        let dependencies_tpl =
+            paths::read_util_file_contents(paths::UtilityPath::DependenciesReadme);
+
+        // pipes would break the markdown table, composer constraints use them (eg. ^7.4 || ^8.0)
+        let escape = |s: &String| s.replace('|', "\\|");
+
+        let kinds = [
+            ("runtime", self.converted_config.dependencies.clone()),
+            ("dev", self.converted_config.dev_dependencies.clone()),
+            ("build", self.converted_config.build_dependencies.clone()),
+        ];
+
+        let dependencies: Vec<Value> = kinds
+            .into_iter()
+            .flat_map(|(kind, dependencies)| {
+                dependencies
+                    .into_iter()
+                    .flatten()
+                    .sorted_by(|a, b| a.name.cmp(&b.name))
+                    .map(move |dependency| (kind, dependency))
+            })
+            .map(|(kind, dependency)| {
+                let source = match &dependency.source {
+                    DependencySource::Git { url, reference } => match reference {
+                        Some(reference) => format!("[git]({}) `{}`", url, reference),
+                        None => format!("[git]({})", url),
+                    },
+                    DependencySource::Path(path) => format!("path `{}`", path),
+                    DependencySource::Url(url) => format!("[url]({})", url),
+                    source => source.to_string(),
+                };
+
+                let mut notes: Vec<String> = vec![];
+                if dependency.optional {
+                    notes.push("optional".to_string());
+                }
+                if !dependency.features.is_empty() {
+                    notes.push(format!(
+                        "features: {}",
+                        dependency
+                            .features
+                            .iter()
+                            .map(|f| format!("`{}`", f))
+                            .join(", ")
                     ));
                 }
-                license.gen_md().unwrap()
-            }
+                if let Some(target) = &dependency.target {
+                    notes.push(format!("only on `{}`", target));
+                }
+
+                json!({
+                    "name": format!("`{}`", dependency.name),
+                    "kind": kind,
+                    "version": dependency
+                        .version
+                        .as_ref()
+                        .map(|v| format!("`{}`", escape(v)))
+                        .unwrap_or_default(),
+                    "resolved": dependency
+                        .resolved
+                        .as_ref()
+                        .map(|v| format!("`{}`", v))
+                        .unwrap_or_default(),
+                    "source": source,
+                    "notes": notes.join(", "),
+                })
+            })
+            .collect();
+
+        if dependencies.is_empty() {
+            return None;
+        }
+
+        self.handlebars
+            .register_template_string("dependencies_tpl", dependencies_tpl)
+            .unwrap();
+
+        Some(
+            self.handlebars
+                .render("dependencies_tpl", &json!({ "dependencies": dependencies }))
+                .unwrap(),
+        )
+    }
+
+    /// Returns the instructions to install the binaries of the project, if it has any
+    fn assemble_install(&mut self) -> Option<String> {
+        let install_tpl = paths::read_util_file_contents(paths::UtilityPath::InstallReadme);
+
+        let binaries: Vec<&Target> = self
+            .converted_config
+            .targets
+            .as_ref()?
+            .iter()
+            .filter(|t| t.kind == TargetKind::Bin)
+            .collect();
+
+        if binaries.is_empty() {
+            return None;
+        }
+
+        let repository_url = self.converted_config.repository.as_ref().map(|r| &r.url);
+        let single = binaries.len() == 1;
+
+        let commands: Vec<String> = binaries
+            .iter()
+            .map(|binary| binary.install_command(repository_url, single))
+            .collect();
+
+        self.handlebars
+            .register_template_string("install_tpl", install_tpl)
+            .unwrap();
+
+        Some(
+            self.handlebars
+                .render(
+                    "install_tpl",
+                    &json!({ "single": single, "commands": commands }),
+                )
+                .unwrap(),
+        )
+    }
+
+    fn assemble_body(&mut self) -> String {
+        let body_tpl = paths::read_util_file_contents(paths::UtilityPath::BodyReadme);
+
+        let mut license = self.converted_config.license.clone();
+        if let Some(license) = license.as_mut() {
+            license.set_urls(|path| self.file_url(path));
+        }
+
+        let licensing = license
+            .as_ref()
+            .map_or("Published under", |license| license.licensing());
+
+        let license = match license {
+            Some(license) if license.name != SupportedLicense::Unknown => license.gen_md().unwrap(),
             Some(_) | None => SupportedLicense::Unknown.to_string(),
         };
 
+        let prerequisites = self.assemble_prerequisites();
+        let env_vars = self.assemble_env_vars();
+        let usage = self.assemble_usage();
+        let features = self.assemble_features();
+        let dependencies = self.assemble_dependencies();
+        let install = self.assemble_install();
+        let docker = self.assemble_docker();
+        let ci = self.assemble_ci();
+        let languages = self.assemble_languages();
+        let structure = self.assemble_structure();
+
         let body = json!({
+            "usage": usage,
+            "features": features,
+            "dependencies": dependencies,
+            "install": install,
+            "prerequisites": prerequisites,
+            "env_vars": env_vars,
             "license": license,
+            "licensing": licensing,
             "repository_url": self.converted_config.repository.as_ref().unwrap().url.clone(),
+            "submodules": self.converted_config.submodules,
+            "lfs_patterns": self.converted_config.lfs_patterns,
+            "docker": docker,
+            "tests": assemble_tests(&self.converted_config.test_commands),
+            "ci": ci,
+            "languages": languages,
+            "structure": structure,
         });
 
         self.handlebars
@@ -98,19 +702,112 @@ impl<'a> ReadmeAssembler<'a> {
         self.handlebars.render("body_tpl", &body).unwrap()
     }
 
+    /// Returns the table of the most active contributors with their activity in the git history, the ones past the
+    /// configured top are only counted
+    fn assemble_authors_table(&mut self, contributors: &Contributors) -> String {
+        let authors_tpl = paths::read_util_file_contents(paths::UtilityPath::AuthorsReadme);
+
+        let authors: Vec<Value> = contributors
+            .iter()
+            .sorted_by_key(|c| Reverse(c.stats.as_ref().map(|s| s.commits)))
+            // if there is an error to generate markdown, just skip this contributor
+            .filter_map(|c| Some(json!({ "author": c.gen_md().ok()?, "stats": c.stats })))
+            .collect();
+
+        let top = self.config.contributors.top.min(authors.len());
+
+        self.handlebars
+            .register_template_string("authors_tpl", authors_tpl)
+            .unwrap();
+
+        self.handlebars
+            .render(
+                "authors_tpl",
+                &json!({ "authors": authors[..top], "more": authors.len() - top }),
+            )
+            .unwrap()
+    }
+
+    /// Returns the gallery of the avatars of the contributors, only when enabled in the config
+    fn assemble_avatars(&mut self) -> Option<String> {
+        let avatars_tpl = paths::read_util_file_contents(paths::UtilityPath::AvatarsReadme);
+
+        if !self.config.contributors.avatars {
+            return None;
+        }
+
+        let avatars: Vec<Value> = self
+            .converted_config
+            .contributors
+            .as_ref()?
+            .iter()
+            // the avatar would give away the email through its hash
+            .filter(|c| {
+                !self
+                    .config
+                    .contributors
+                    .is_private(c.name.as_ref(), c.email.as_ref())
+            })
+            .filter_map(|c| {
+                Some(json!({
+                    "name": c.name.as_ref()?,
+                    "url": c.url.as_ref().filter(|_| self.config.contributors.emails != EmailPrivacy::NameOnly),
+                    "src": c.avatar_url(96)?,
+                }))
+            })
+            .collect();
+
+        if avatars.is_empty() {
+            return None;
+        }
+
+        self.handlebars
+            .register_template_string("avatars_tpl", avatars_tpl)
+            .unwrap();
+
+        Some(
+            self.handlebars
+                .render("avatars_tpl", &json!({ "avatars": avatars }))
+                .unwrap(),
+        )
+    }
+
     fn assemble_footer(&mut self) -> String {
         let footer_tpl = paths::read_util_file_contents(paths::UtilityPath::FooterReadme);
 
-        let contrib_section;
+        // the emails are published according to the privacy settings of the project
+        let contributors: Option<Contributors> =
+            self.converted_config
+                .contributors
+                .as_ref()
+                .map(|contributors| {
+                    contributors
+                        .iter()
+                        .map(|c| {
+                            let privacy = self
+                                .config
+                                .contributors
+                                .email_privacy(c.name.as_ref(), c.email.as_ref());
+                            c.with_privacy(privacy)
+                        })
+                        .collect()
+                });
+
+        let mut contrib_section;
         let repository = self.converted_config.repository.as_ref().unwrap();
         if repository.platform == RepositoryPlatform::Github {
             contrib_section = Some(repository.gen_md().unwrap());
         } else {
-            contrib_section = match self.converted_config.contributors.clone() {
+            contrib_section = match contributors.clone() {
+                // contrib.rocks doesn't apply, the activity of the contributors is shown instead
+                Some(contributors) if contributors.iter().any(|c| c.stats.is_some()) => {
+                    Some(self.assemble_authors_table(&contributors))
+                }
                 Some(contributors) => {
                     let mut authors = String::new();
 
-                    for c in contributors {
+                    // the contributors are listed as merged, the most active first
+                    for c in contributors.iter() {
                         let author = "- ".to_string();
 
                         match c.gen_md() {
@@ -128,6 +825,14 @@ impl<'a> ReadmeAssembler<'a> {
                 }
                 None => None,
             };
+
+            if let Some(avatars) = self.assemble_avatars() {
+                contrib_section = Some(format!(
+                    "{}\n{}",
+                    avatars,
+                    contrib_section.unwrap_or_default()
+                ));
+            }
         }
 
         let funding: Option<String> = match self.converted_config.funding.clone() {
@@ -153,6 +858,7 @@ impl<'a> ReadmeAssembler<'a> {
         let footer = json!({
             "name": self.converted_config.name.clone(),
             "authors": contrib_section.unwrap_or("".to_string()),
+            "contributors": contributors.as_ref().map(|c| c.iter().collect::<Vec<_>>()),
             "funding": funding,
         });
 
@@ -172,8 +878,15 @@ impl<'a> ReadmeAssembler<'a> {
         };
 
         let techs: Vec<String> = scan_techs(path).unwrap();
-        let deps: Vec<String> =
-            scan_dependencies(self.converted_config.dependencies.clone().unwrap()).unwrap();
+        let deps: Vec<String> = scan_dependencies(
+            self.converted_config.dependencies.clone().unwrap(),
+            &self
+                .converted_config
+                .locked_packages
+                .clone()
+                .unwrap_or_default(),
+        )
+        .unwrap();
 
         let to_make_shields: Vec<String> = techs.iter().chain(deps.iter()).cloned().collect();
 
@@ -0,0 +1,215 @@
+//! The optional `.writeme.toml` file at the project root lets the user tune what gets generated, every setting has a
+//! default so the file may contain only the ones to change.
+//!
+//! ```toml
+//! [structure]
+//! depth = 2
+//!
+//! [structure.descriptions]
+//! "src/converter" = "Parsers of the supported config files"
+//!
+//! [contributors]
+//! top = 10
+//! exclude = ["*[bot]*", "ci@example.com"]
+//! avatars = true
+//! emails = "obfuscate"
+//! private = ["*@example.com"]
+//!
+//! [changelog]
+//! enabled = true
+//!
+//! [repository]
+//! branch = "develop"
+//!
+//! [repository.platforms]
+//! "git.example.com" = "gitea"
+//! ```
+
+use std::{collections::HashMap, fs, path::Path};
+
+use anyhow::{anyhow, Error};
+use regex::RegexSet;
+use serde::Deserialize;
+
+use crate::elements::repository::RepositoryPlatform;
+
+/// The name of the configuration file, looked up at the project root
+pub const CONFIG_FILE: &str = ".writeme.toml";
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub structure: StructureConfig,
+    pub contributors: ContributorsConfig,
+    pub changelog: ChangelogConfig,
+    pub repository: RepositoryConfig,
+}
+
+/// Settings of the "Project Structure" section
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct StructureConfig {
+    /// How many levels of directories the tree shows
+    pub depth: usize,
+    /// Descriptions of the directories by their path relative to the project root, e.g. `src/converter`
+    pub descriptions: HashMap<String, String>,
+}
+
+impl Default for StructureConfig {
+    fn default() -> Self {
+        StructureConfig {
+            depth: 2,
+            descriptions: HashMap::new(),
+        }
+    }
+}
+
+/// Settings of the authors list
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct ContributorsConfig {
+    /// How many of the most active contributors the authors table shows, the others are only counted
+    pub top: usize,
+    /// The names or emails of the git authors left out of the list, `*` matches any text
+    ///
+    /// The common bots are excluded by default, setting the list replaces them
+    pub exclude: Patterns,
+    /// Whether to show the avatars of the contributors when the repository doesn't get the contrib.rocks gallery
+    pub avatars: bool,
+    /// How the emails of the contributors are published
+    pub emails: EmailPrivacy,
+    /// The names or emails of the contributors whose email is never published, `*` matches any text
+    pub private: Patterns,
+}
+
+/// Settings of the CHANGELOG
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct ChangelogConfig {
+    /// Whether to write the changelog when the project has none yet, an existing one is always kept up to date
+    pub enabled: bool,
+}
+
+/// Settings of the links to the repository of the project
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct RepositoryConfig {
+    /// The branch the links to the files of the project point to, detected from the repository by default
+    pub branch: Option<String>,
+    /// The platforms of the self-hosted instances by host, those not listed are expected to lay out their pages as
+    /// GitLab does
+    pub platforms: HashMap<String, RepositoryPlatform>,
+}
+
+/// How the emails of the contributors are published in the README
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum EmailPrivacy {
+    /// The emails are left out, a contributor is linked to its profile page only
+    #[default]
+    Omit,
+    /// A contributor without a profile page is linked to a `mailto:` of its email
+    Mailto,
+    /// As `mailto`, with the email written as HTML entities to get past the simplest scrapers
+    Obfuscate,
+    /// The contributors are listed by name only, without any link
+    NameOnly,
+}
+
+impl Default for ContributorsConfig {
+    fn default() -> Self {
+        ContributorsConfig {
+            top: 10,
+            exclude: Patterns::from(vec![
+                "*[bot]*".to_string(),
+                "dependabot*".to_string(),
+                "renovate*".to_string(),
+                "github-actions*".to_string(),
+                "greenkeeper*".to_string(),
+                "snyk-bot*".to_string(),
+            ]),
+            avatars: false,
+            emails: EmailPrivacy::default(),
+            private: Patterns::default(),
+        }
+    }
+}
+
+impl ContributorsConfig {
+    /// Whether the given name or email matches one of the exclude patterns, case insensitively
+    pub fn excludes(&self, value: &str) -> bool {
+        self.exclude.is_match(value)
+    }
+
+    /// Whether the contributor with the given name and email is in the private list
+    pub fn is_private(&self, name: Option<&String>, email: Option<&String>) -> bool {
+        [name, email]
+            .iter()
+            .flatten()
+            .any(|value| self.private.is_match(value))
+    }
+
+    /// Returns how the email of the contributor with the given name and email is published, the contributors in
+    /// the private list never have it published
+    pub fn email_privacy(&self, name: Option<&String>, email: Option<&String>) -> EmailPrivacy {
+        match (self.is_private(name, email), self.emails) {
+            (true, EmailPrivacy::NameOnly) => EmailPrivacy::NameOnly,
+            (true, _) => EmailPrivacy::Omit,
+            (false, emails) => emails,
+        }
+    }
+}
+
+/// Patterns matching a whole name or email case insensitively, `*` matches any text, they are compiled once when the
+/// config is loaded
+#[derive(Debug, Clone, Deserialize)]
+#[serde(from = "Vec<String>")]
+pub struct Patterns(RegexSet);
+
+impl Default for Patterns {
+    fn default() -> Self {
+        Patterns(RegexSet::empty())
+    }
+}
+
+impl From<Vec<String>> for Patterns {
+    fn from(patterns: Vec<String>) -> Self {
+        let patterns = patterns.iter().map(|pattern| {
+            let regex = pattern
+                .split('*')
+                .map(regex::escape)
+                .collect::<Vec<String>>()
+                .join(".*");
+
+            format!("(?i)^{}$", regex)
+        });
+
+        // the patterns are escaped, so they are always valid
+        Patterns(RegexSet::new(patterns).unwrap())
+    }
+}
+
+impl Patterns {
+    /// Whether the value matches one of the patterns
+    pub fn is_match(&self, value: &str) -> bool {
+        self.0.is_match(value)
+    }
+}
+
+impl Config {
+    /// Loads the configuration of the project, the default one is returned when the project has no config file
+    pub fn load(project_location: &str) -> Result<Config, Error> {
+        let path = Path::new(project_location).join(CONFIG_FILE);
+
+        if !path.exists() {
+            return Ok(Config::default());
+        }
+
+        let contents = fs::read_to_string(&path)?;
+
+        match toml::from_str(&contents) {
+            Ok(config) => Ok(config),
+            Err(e) => Err(anyhow!("Invalid {}: {}", CONFIG_FILE, e)),
+        }
+    }
+}
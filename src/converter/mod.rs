@@ -16,24 +16,44 @@ use std::{
 use anyhow::{anyhow, Error};
 use serde::Serialize;
 use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
 use strum::IntoEnumIterator;
 
 pub mod parsers {
     pub mod cargo_toml;
     pub mod composer_json;
+    pub mod docker_compose;
+    pub mod dockerfile;
+    pub mod justfile;
+    pub mod makefile;
     pub mod package_json;
+    pub mod taskfile_yml;
 }
 
 use crate::{
+    config::EmailPrivacy,
     elements::{
+        cargo::{Feature, Target},
+        changelog::Release,
+        ci::Workflow,
+        docker::Docker,
+        env::EnvVar,
         funding::{Funding, FundingType, Fundings},
+        language::Language,
         license::License,
-        repository::Repository,
+        lockfile::LockedPackage,
+        repository::{Repository, Submodule, VersionTag},
+        script::Script,
+        structure::Directory,
+        toolchain::Prerequisite,
     },
     utils::{paths, trim, GenMarkdown},
 };
 
-use self::parsers::{cargo_toml, composer_json, package_json};
+use self::parsers::{
+    cargo_toml, composer_json, docker_compose, dockerfile, justfile, makefile, package_json,
+    taskfile_yml,
+};
 
 // The base Component trait defines operations that can be altered by
 // decorators.
@@ -67,6 +87,7 @@ impl Component for ConcreteComponent {
             name: None,
             description: None,
             version: None,
+            version_tag: None,
             contributors: None,
             license: None,
             keywords: None,
@@ -76,6 +97,23 @@ impl Component for ConcreteComponent {
             dev_dependencies: None,
             build_dependencies: None,
             funding: None,
+            docker: None,
+            scripts: None,
+            rust_version: None,
+            features: None,
+            targets: None,
+            locked_packages: None,
+            prerequisites: None,
+            env_vars: None,
+            test_commands: None,
+            workflows: None,
+            languages: None,
+            primary_language: None,
+            structure: None,
+            releases: None,
+            default_branch: None,
+            submodules: None,
+            lfs_patterns: None,
         })
     }
 
@@ -84,13 +122,15 @@ impl Component for ConcreteComponent {
             name: contributor["name"].as_str().map(|s| s.to_string()),
             email: contributor["email"].as_str().map(|s| s.to_string()),
             url: contributor["url"].as_str().map(|s| s.to_string()),
+            stats: None,
         })
     }
 
     fn parse_dependency(&self, key: &String, value: &Value) -> Result<Dependency, Error> {
         Ok(Dependency {
             name: key.to_string(),
-            version: Some(value.to_string()),
+            version: value.as_str().map(|s| s.to_string()),
+            ..Dependency::default()
         })
     }
 
@@ -138,6 +178,11 @@ pub enum SupportedFile {
     ComposerJson,
     PackageJson,
     CargoToml,
+    Dockerfile,
+    DockerCompose,
+    Makefile,
+    Justfile,
+    TaskfileYml,
 }
 
 impl SupportedFile {
@@ -146,6 +191,15 @@ impl SupportedFile {
             "composer.json" => Ok(SupportedFile::ComposerJson),
             "package.json" => Ok(SupportedFile::PackageJson),
             "Cargo.toml" => Ok(SupportedFile::CargoToml),
+            "Dockerfile" => Ok(SupportedFile::Dockerfile),
+            "docker-compose.yml" | "docker-compose.yaml" | "compose.yml" | "compose.yaml" => {
+                Ok(SupportedFile::DockerCompose)
+            }
+            "Makefile" | "makefile" | "GNUmakefile" => Ok(SupportedFile::Makefile),
+            "justfile" | "Justfile" | ".justfile" => Ok(SupportedFile::Justfile),
+            "Taskfile.yml" | "Taskfile.yaml" | "taskfile.yml" | "taskfile.yaml" => {
+                Ok(SupportedFile::TaskfileYml)
+            }
             _ => Err(anyhow!("Unsupported file type")),
         }
     }
@@ -157,34 +211,95 @@ impl Display for SupportedFile {
             SupportedFile::ComposerJson => "composer.json",
             SupportedFile::PackageJson => "package.json",
             SupportedFile::CargoToml => "Cargo.toml",
+            SupportedFile::Dockerfile => "Dockerfile",
+            SupportedFile::DockerCompose => "docker-compose.yml",
+            SupportedFile::Makefile => "Makefile",
+            SupportedFile::Justfile => "justfile",
+            SupportedFile::TaskfileYml => "Taskfile.yml",
         };
 
         write!(f, "{}", file_type)
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+/// Where a dependency is fetched from
+pub enum DependencySource {
+    /// The default registry of the ecosystem (crates.io, npm, packagist) or the named one
+    #[default]
+    Registry,
+    /// A git repository, optionally pinned to a branch, tag or revision
+    Git {
+        url: String,
+        reference: Option<String>,
+    },
+    /// A local directory or archive
+    Path(String),
+    /// A remote archive
+    Url(String),
+    /// Inherited from the workspace (eg. `{ workspace = true }` or `workspace:*`)
+    Workspace,
+    /// Provided by the platform rather than installed (eg. `php` or `ext-json` in composer.json)
+    Platform,
+}
+
+impl Display for DependencySource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DependencySource::Registry => write!(f, "registry"),
+            DependencySource::Git { url, reference } => match reference {
+                Some(reference) => write!(f, "git {}#{}", url, reference),
+                None => write!(f, "git {}", url),
+            },
+            DependencySource::Path(path) => write!(f, "path {}", path),
+            DependencySource::Url(url) => write!(f, "url {}", url),
+            DependencySource::Workspace => write!(f, "workspace"),
+            DependencySource::Platform => write!(f, "platform"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
 /// Holds the information of a dependency in a config file
 pub struct Dependency {
     /// The name of the dependency
     pub name: String,
 
-    /// The version of the dependency, it may be missing!
-    version: Option<String>,
+    /// The version requirement of the dependency, it may be missing! (eg. git and path dependencies)
+    pub version: Option<String>,
+
+    pub source: DependencySource,
+
+    /// Whether the dependency is installed only on demand (eg. through a Cargo feature)
+    pub optional: bool,
+
+    /// The features of the dependency enabled by the project, only for Cargo.toml
+    pub features: Vec<String>,
+
+    /// The platform the dependency is restricted to, e.g. `cfg(windows)` for `[target.'cfg(windows)'.dependencies]`
+    pub target: Option<String>,
+
+    /// The exact version installed according to the lockfile of the project, if any
+    pub resolved: Option<String>,
+
+    /// The key the dependency is declared under when it's renamed, only for Cargo.toml (eg. `futures01` for
+    /// `futures01 = { package = "futures", version = "0.1" }`)
+    pub rename: Option<String>,
 }
 
 impl Display for Dependency {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "{} {}",
+            "{} {} ({})",
             self.name,
-            self.version.as_ref().unwrap_or(&"None".to_string())
+            self.version.as_ref().unwrap_or(&"None".to_string()),
+            self.source
         )
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 /// This Vec variant is needed to implement the Display trait for the Vec<T> scenarios
 ///
 /// Reference: https://stackoverflow.com/a/30633256/11802618
@@ -200,6 +315,12 @@ impl Display for Dependencies {
     }
 }
 
+impl Dependencies {
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
 impl FromIterator<Dependency> for Dependencies {
     fn from_iter<I: IntoIterator<Item = Dependency>>(iter: I) -> Self {
         let mut dependencies = Vec::new();
@@ -224,19 +345,242 @@ pub struct Contributor {
     pub name: Option<String>,
     pub email: Option<String>,
     pub url: Option<String>,
+    /// The activity of the contributor in the git history, only for the contributors found there
+    pub stats: Option<ContributorStats>,
+}
+
+/// The activity of a contributor in the git history of the project
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct ContributorStats {
+    pub commits: usize,
+    /// The lines added by the commits, the merge commits are not counted
+    pub additions: usize,
+    /// The lines removed by the commits, the merge commits are not counted
+    pub deletions: usize,
+    /// The day of the first commit, in the YYYY-MM-DD format
+    pub first_commit: String,
+    /// The day of the last commit, in the YYYY-MM-DD format
+    pub last_commit: String,
 }
 
-// two contributors are equal if they have the same email
+impl ContributorStats {
+    /// Creates the stats of a single commit made on the given day
+    pub fn new(date: &str, additions: usize, deletions: usize) -> Self {
+        ContributorStats {
+            commits: 1,
+            additions,
+            deletions,
+            first_commit: date.to_string(),
+            last_commit: date.to_string(),
+        }
+    }
+
+    /// Adds the activity of other stats to these ones, e.g. of another identity of the same person
+    pub fn combine(&mut self, other: &ContributorStats) {
+        self.commits += other.commits;
+        self.additions += other.additions;
+        self.deletions += other.deletions;
+
+        // the dates are ISO formatted, they compare as strings
+        if other.first_commit < self.first_commit {
+            self.first_commit = other.first_commit.clone();
+        }
+        if other.last_commit > self.last_commit {
+            self.last_commit = other.last_commit.clone();
+        }
+    }
+}
+
+impl Contributor {
+    /// Returns the email as written, without the `mailto:` prefix or the angle brackets it may come with
+    pub fn email_address(&self) -> Option<String> {
+        let email = self
+            .email
+            .as_ref()?
+            .trim()
+            .trim_start_matches("mailto:")
+            .trim_matches(|c| c == '<' || c == '>');
+
+        match email.is_empty() {
+            true => None,
+            false => Some(email.to_string()),
+        }
+    }
+
+    /// Returns the email lowercased, the GitHub noreply addresses lose the user id prefix they may have
+    ///
+    /// e.g. 1234567+jane@users.noreply.github.com -> **jane@users.noreply.github.com**
+    pub fn normalized_email(&self) -> Option<String> {
+        let email = self.email_address()?.to_lowercase();
+
+        match email.split_once('+') {
+            Some((id, rest))
+                if rest.ends_with("@users.noreply.github.com")
+                    && id.chars().all(|c| c.is_ascii_digit()) =>
+            {
+                Some(rest.to_string())
+            }
+            _ => Some(email),
+        }
+    }
+
+    /// Returns the name lowercased and with its whitespaces collapsed
+    pub fn normalized_name(&self) -> Option<String> {
+        let name = self
+            .name
+            .as_ref()?
+            .split_whitespace()
+            .collect::<Vec<&str>>()
+            .join(" ")
+            .to_lowercase();
+
+        Some(name).filter(|n| !n.is_empty())
+    }
+
+    /// Returns the profile page of the contributor guessed from the noreply address of a platform, e.g.
+    ///
+    /// 1234567+jane@users.noreply.github.com -> **https://github.com/jane**
+    pub fn profile_url(&self) -> Option<String> {
+        let email = self.normalized_email()?;
+        let (user, domain) = email.split_once('@')?;
+
+        match domain {
+            "users.noreply.github.com" => Some(format!("https://github.com/{}", user)),
+            // the recent GitLab addresses are in the ID-user form, the older ones hold the username only
+            "users.noreply.gitlab.com" => Some(format!(
+                "https://gitlab.com/{}",
+                match user.split_once('-') {
+                    Some((id, u)) if id.chars().all(|c| c.is_ascii_digit()) => u,
+                    _ => user,
+                }
+            )),
+            "noreply.codeberg.org" => Some(format!("https://codeberg.org/{}", user)),
+            _ => None,
+        }
+    }
+
+    /// Returns the avatar of the contributor, the GitHub one for the GitHub noreply addresses and the Gravatar
+    /// one otherwise, the hash of the email is computed locally
+    ///
+    /// Reference: https://docs.gravatar.com/api/avatars/images/
+    pub fn avatar_url(&self, size: u32) -> Option<String> {
+        let email = self.normalized_email()?;
+
+        if let Some(user) = email.strip_suffix("@users.noreply.github.com") {
+            return Some(format!("https://github.com/{}.png?size={}", user, size));
+        }
+
+        let hash: String = Sha256::digest(email.as_bytes())
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect();
+
+        Some(format!(
+            "https://gravatar.com/avatar/{}?s={}&d=identicon",
+            hash, size
+        ))
+    }
+
+    /// Returns the contributor as it can be published in the README, the email is left out or turned into a
+    /// `mailto:` link according to the privacy mode
+    pub fn with_privacy(&self, privacy: EmailPrivacy) -> Contributor {
+        // the email is published as the contributor wrote it, the normalized one is for comparisons only
+        let email = self.email_address();
+
+        let email = match privacy {
+            EmailPrivacy::Omit | EmailPrivacy::NameOnly => None,
+            EmailPrivacy::Mailto => email.map(|e| format!("mailto:{}", e)),
+            // every character as a decimal HTML entity, markdown decodes them in the link target
+            EmailPrivacy::Obfuscate => email.map(|e| {
+                format!("mailto:{}", e)
+                    .chars()
+                    .map(|c| format!("&#{};", c as u32))
+                    .collect()
+            }),
+        };
+
+        Contributor {
+            name: self.name.clone(),
+            email,
+            url: self
+                .url
+                .clone()
+                .filter(|_| privacy != EmailPrivacy::NameOnly),
+            stats: self.stats.clone(),
+        }
+    }
+
+    /// Whether the two contributors are likely the same person, they share the email or the full name when one of
+    /// them has no email, two emails of the same person are told by the mailmap only
+    ///
+    /// Unlike the equality, a contributor without email matches the ones with an email, so the people are merged
+    /// with this method rather than `==` or a set
+    pub fn same_person(&self, other: &Contributor) -> bool {
+        let same_email = self.normalized_email().is_some()
+            && self.normalized_email() == other.normalized_email();
+        let same_name = self.normalized_name().is_some()
+            && self.normalized_name() == other.normalized_name()
+            && (self.normalized_email().is_none() || other.normalized_email().is_none());
+
+        same_email || same_name
+    }
+
+    /// Merges the contributors being the same person, each person keeps the place it is first seen at and the
+    /// fields missing there are taken from the other identities
+    pub fn merge_identities<I: IntoIterator<Item = Contributor>>(
+        contributors: I,
+    ) -> Vec<Contributor> {
+        let mut merged: Vec<Contributor> = vec![];
+
+        for contributor in contributors {
+            if contributor.normalized_name().is_none() && contributor.normalized_email().is_none() {
+                continue;
+            }
+
+            match merged.iter_mut().find(|m| m.same_person(&contributor)) {
+                Some(person) => {
+                    person.name = person.name.take().or(contributor.name);
+                    person.email = person.email.take().or(contributor.email);
+                    person.url = person.url.take().or(contributor.url);
+                    person.stats = match (person.stats.take(), contributor.stats) {
+                        (Some(mut stats), Some(other)) => {
+                            stats.combine(&other);
+                            Some(stats)
+                        }
+                        (stats, other) => stats.or(other),
+                    };
+                }
+                None => merged.push(contributor),
+            }
+        }
+
+        for person in merged.iter_mut() {
+            person.url = person.url.take().or_else(|| person.profile_url());
+        }
+
+        merged
+    }
+}
+
+// two contributors are the same identity if they have the same email, or the same name when both have no email, a
+// person may have more than one identity (see `Contributor::same_person`)
 impl PartialEq for Contributor {
     fn eq(&self, other: &Self) -> bool {
-        self.email == other.email
+        match (self.normalized_email(), other.normalized_email()) {
+            (Some(email), Some(other_email)) => email == other_email,
+            (None, None) => self.normalized_name() == other.normalized_name(),
+            _ => false,
+        }
     }
 }
 
-// Hash only by email
+// Hash by the same identity used for equality
 impl Hash for Contributor {
     fn hash<H: Hasher>(&self, state: &mut H) {
-        self.email.hash(state);
+        match self.normalized_email() {
+            Some(email) => email.hash(state),
+            None => self.normalized_name().hash(state),
+        }
     }
 }
 
@@ -267,7 +611,7 @@ impl GenMarkdown for Contributor {
                 .unwrap();
 
             // extract the url field from the url or email field, at least one of them is present if we are here
-            let url = self.url.as_ref().unwrap_or(self.email.as_ref().unwrap());
+            let url = self.url.as_ref().or(self.email.as_ref()).unwrap();
 
             let data: Value = json!({
                 "name": self.name.as_ref().unwrap(),
@@ -297,6 +641,13 @@ impl Display for Contributors {
     }
 }
 
+impl Contributors {
+    /// Returns an iterator over the contributors keeping their order, unlike consuming the list
+    pub fn iter(&self) -> std::slice::Iter<'_, Contributor> {
+        self.0.iter()
+    }
+}
+
 impl FromIterator<Contributor> for Contributors {
     fn from_iter<I: IntoIterator<Item = Contributor>>(iter: I) -> Self {
         let mut contributors = Vec::new();
@@ -323,6 +674,8 @@ pub struct ConverterOutput {
     pub name: Option<String>,
     pub description: Option<String>,
     pub version: Option<String>,
+    /// latest semver tag of the repository, only for the .git folder
+    pub version_tag: Option<VersionTag>,
     pub contributors: Option<Contributors>,
     pub license: Option<License>,
     pub keywords: Option<Vec<String>>,
@@ -342,6 +695,57 @@ pub struct ConverterOutput {
 
     /// funding of the project, not every config file supports this (eg. Cargo.toml)
     pub funding: Option<Fundings>,
+
+    /// docker image and compose services of the project, only for Dockerfile and docker-compose.yml
+    pub docker: Option<Docker>,
+
+    /// runnable tasks of the project (eg. package.json scripts, Makefile targets)
+    pub scripts: Option<Vec<Script>>,
+
+    /// minimum supported Rust version of the project, only for Cargo.toml
+    pub rust_version: Option<String>,
+
+    /// features of the project, only for Cargo.toml
+    pub features: Option<Vec<Feature>>,
+
+    /// binaries and examples of the project, only for Cargo.toml
+    pub targets: Option<Vec<Target>>,
+
+    /// packages pinned by the lockfiles of the project, including the transitive ones
+    pub locked_packages: Option<Vec<LockedPackage>>,
+
+    /// tools to install before running the project, from the pin files (eg. .nvmrc) and the manifests
+    pub prerequisites: Option<Vec<Prerequisite>>,
+
+    /// environment variables documented in the env templates of the project (eg. .env.example)
+    pub env_vars: Option<Vec<EnvVar>>,
+
+    /// commands running the tests of the project, from the manifests and the detected test frameworks
+    pub test_commands: Option<Vec<Script>>,
+
+    /// CI workflows of the project (eg. GitHub Actions workflows, .gitlab-ci.yml)
+    pub workflows: Option<Vec<Workflow>>,
+
+    /// share of the source code written in each language, sorted by share
+    pub languages: Option<Vec<Language>>,
+
+    /// the language the project is written in, only when no manifest tells it (eg. package.json)
+    pub primary_language: Option<String>,
+
+    /// directories of the project, sorted as they appear in a tree
+    pub structure: Option<Vec<Directory>>,
+
+    /// conventional commits of the project grouped by the tag they were released under, the latest first
+    pub releases: Option<Vec<Release>>,
+
+    /// the branch the links to the files of the repository point to, e.g. main
+    pub default_branch: Option<String>,
+
+    /// submodules of the repository, they are cloned along with it
+    pub submodules: Option<Vec<Submodule>>,
+
+    /// patterns of the files stored with Git LFS (eg. *.psd)
+    pub lfs_patterns: Option<Vec<String>>,
 }
 
 impl ConverterOutput {
@@ -352,6 +756,7 @@ impl ConverterOutput {
             name: None,
             description: None,
             version: None,
+            version_tag: None,
             contributors: None,
             license: None,
             keywords: None,
@@ -361,6 +766,23 @@ impl ConverterOutput {
             dev_dependencies: None,
             build_dependencies: None,
             funding: None,
+            docker: None,
+            scripts: None,
+            rust_version: None,
+            features: None,
+            targets: None,
+            locked_packages: None,
+            prerequisites: None,
+            env_vars: None,
+            test_commands: None,
+            workflows: None,
+            languages: None,
+            primary_language: None,
+            structure: None,
+            releases: None,
+            default_branch: None,
+            submodules: None,
+            lfs_patterns: None,
         }
     }
 
@@ -369,6 +791,7 @@ impl ConverterOutput {
         self.name = self.name.take().map(|s| trim(s).unwrap());
         self.description = self.description.take().map(|s| trim(s).unwrap());
         self.version = self.version.take().map(|s| trim(s).unwrap());
+        self.rust_version = self.rust_version.take().map(|s| trim(s).unwrap());
         // self.license = self.license.take().map(|s| trim(s).unwrap());
         self.homepage_url = self.homepage_url.take().map(|s| trim(s).unwrap());
     }
@@ -391,6 +814,16 @@ impl Converter {
 
     //     component.convert(contents)
     // }
+    /// Whether the file is a package manifest of a language ecosystem (eg. package.json, Cargo.toml)
+    pub fn is_manifest(path: &str) -> bool {
+        matches!(
+            Converter::get_filename(path).map(SupportedFile::from_str),
+            Some(Ok(SupportedFile::ComposerJson
+                | SupportedFile::PackageJson
+                | SupportedFile::CargoToml))
+        )
+    }
+
     /// Gets the filename from a path string
     fn get_filename(path: &str) -> Option<&str> {
         let path = Path::new(path);
@@ -419,6 +852,54 @@ impl Converter {
             SupportedFile::CargoToml => {
                 cargo_toml::CargoToml::new().convert(path.to_string(), contents)
             }
+            SupportedFile::Dockerfile => {
+                dockerfile::Dockerfile::new().convert(path.to_string(), contents)
+            }
+            SupportedFile::DockerCompose => {
+                docker_compose::DockerCompose::new().convert(path.to_string(), contents)
+            }
+            SupportedFile::Makefile => {
+                makefile::Makefile::new().convert(path.to_string(), contents)
+            }
+            SupportedFile::Justfile => {
+                justfile::Justfile::new().convert(path.to_string(), contents)
+            }
+            SupportedFile::TaskfileYml => {
+                taskfile_yml::TaskfileYml::new().convert(path.to_string(), contents)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn contributor(email: &str) -> Contributor {
+        Contributor {
+            name: None,
+            email: Some(email.to_string()),
+            url: None,
+            stats: None,
+        }
+    }
+
+    #[test]
+    fn guesses_the_gitlab_profile_from_the_noreply_address() {
+        let cases = [
+            (
+                "1234567-jane-doe@users.noreply.gitlab.com",
+                "https://gitlab.com/jane-doe",
+            ),
+            (
+                "jane-doe@users.noreply.gitlab.com",
+                "https://gitlab.com/jane-doe",
+            ),
+            ("jane@users.noreply.gitlab.com", "https://gitlab.com/jane"),
+        ];
+
+        for (email, expected) in cases {
+            assert_eq!(contributor(email).profile_url().as_deref(), Some(expected));
         }
     }
 }
@@ -2,8 +2,16 @@ use serde_json::Value;
 
 use anyhow::{anyhow, Error, Ok};
 
-use crate::converter::{
-    Component, Contributor, ConverterOutput, Decorator, Dependency, Funding, License, Repository,
+use crate::{
+    converter::{
+        Component, Contributor, ConverterOutput, Decorator, Dependencies, Dependency,
+        DependencySource, Funding, License, Repository,
+    },
+    elements::{
+        cargo::{Feature, Target, TargetKind},
+        script::Script,
+        toolchain::Prerequisite,
+    },
 };
 
 /// The Cargo.toml file relevant contents
@@ -81,6 +89,35 @@ impl Decorator for CargoToml {
     }
 }
 
+impl CargoToml {
+    /// Parses the dependencies of the given kind (eg. `dev-dependencies`) including the platform specific ones
+    /// declared in the `[target.'cfg(..)'.<kind>]` tables
+    fn parse_dependencies(&self, json: &Value, kind: &str) -> Dependencies {
+        let mut tables: Vec<(Option<String>, &Value)> = vec![(None, &json[kind])];
+
+        if let Some(targets) = json["target"].as_object() {
+            for (target, table) in targets {
+                tables.push((Some(target.to_string()), &table[kind]));
+            }
+        }
+
+        tables
+            .into_iter()
+            .filter_map(|(target, table)| table.as_object().map(|t| (target, t)))
+            .flat_map(|(target, table)| {
+                table.iter().filter_map(move |(key, value)| {
+                    let dependency = self.parse_dependency(key, value);
+
+                    dependency.ok().map(|d| Dependency {
+                        target: target.clone(),
+                        ..d
+                    })
+                })
+            })
+            .collect()
+    }
+}
+
 impl Component for CargoToml {
     fn parse_contributor(&self, contributor: &Value) -> Result<Contributor, Error> {
         let as_str = contributor.as_str();
@@ -100,44 +137,72 @@ impl Component for CargoToml {
 
         let url = attrs.get(2).map(|s| s.to_string());
 
-        Ok(Contributor { name, email, url })
+        Ok(Contributor {
+            name,
+            email,
+            url,
+            stats: None,
+        })
     }
 
     fn parse_dependency(&self, key: &String, value: &Value) -> Result<Dependency, Error> {
-        if value.is_string() {
+        // the short form `serde = "1.0"` is a registry dependency
+        if let Some(version) = value.as_str() {
             return Ok(Dependency {
                 name: key.to_string(),
-                version: Some(value.to_string()),
+                version: Some(version.to_string()),
+                ..Dependency::default()
             });
-        } else if value.is_object() {
-            let version = value["package"]["version"].as_str();
-
-            // there must be a better way to do the below but my rust skills are 🥴
-            // future us: improve this!
-            if version.is_some() {
-                return Ok(Dependency {
-                    name: key.to_string(),
-                    version: Some(version.unwrap().to_string()),
-                });
-            } else {
-                return Ok(Dependency {
-                    name: key.to_string(),
-                    version: None,
-                });
-            }
         }
 
-        Err(anyhow!(
-            "Could not parse dependency! Key: {}, Value: {}",
-            key,
-            value
-        ))
+        if !value.is_object() {
+            return Err(anyhow!(
+                "Could not parse dependency! Key: {}, Value: {}",
+                key,
+                value
+            ));
+        }
+
+        let source = if value["workspace"].as_bool().unwrap_or(false) {
+            DependencySource::Workspace
+        } else if let Some(url) = value["git"].as_str() {
+            DependencySource::Git {
+                url: url.to_string(),
+                reference: ["tag", "branch", "rev"]
+                    .iter()
+                    .find_map(|r| value[r].as_str())
+                    .map(|r| r.to_string()),
+            }
+        } else if let Some(path) = value["path"].as_str() {
+            DependencySource::Path(path.to_string())
+        } else {
+            DependencySource::Registry
+        };
+
+        Ok(Dependency {
+            // a renamed dependency is imported with the key but fetched with the package name
+            name: value["package"].as_str().unwrap_or(key).to_string(),
+            version: value["version"].as_str().map(|v| v.to_string()),
+            source,
+            optional: value["optional"].as_bool().unwrap_or(false),
+            features: value["features"]
+                .as_array()
+                .map(|f| {
+                    f.iter()
+                        .filter_map(|f| f.as_str().map(|f| f.to_string()))
+                        .collect()
+                })
+                .unwrap_or_default(),
+            target: None,
+            resolved: None,
+            rename: value["package"].as_str().map(|_| key.to_string()),
+        })
     }
 
     fn convert(&self, file_path: String, file_contents: String) -> Result<ConverterOutput, Error> {
         let mut output = ConverterOutput::empty();
 
-        output.source_config_file_path = file_path;
+        output.source_config_file_path = file_path.clone();
 
         let json: Value = toml::from_str(file_contents.as_str()).unwrap();
 
@@ -194,38 +259,92 @@ impl Component for CargoToml {
             .map(|v| v.iter().map(|s| s.to_string()).collect());
         output.homepage_url = Some(json["package"]["homepage"].to_string());
 
-        output.dependencies = json["dependencies"].as_object().map(|v| {
-            v.iter()
-                // .map(|(k, v)| self.parse_dependency(k, v))
-                .filter_map(|(key, value)| {
-                    let dependency = self.parse_dependency(key, value);
+        output.dependencies = Some(self.parse_dependencies(&json, "dependencies"));
+        output.dev_dependencies = Some(self.parse_dependencies(&json, "dev-dependencies"));
+        output.build_dependencies = Some(self.parse_dependencies(&json, "build-dependencies"));
 
-                    dependency.ok()
-                })
-                .collect()
+        // rust-version may be inherited from the workspace ({ workspace = true }), in that case it's not a string
+        output.rust_version = json["package"]["rust-version"]
+            .as_str()
+            .map(|v| v.to_string());
+
+        output.prerequisites = output.rust_version.as_ref().map(|version| {
+            vec![Prerequisite {
+                minimum: true,
+                ..Prerequisite::new("rust", version, &file_path)
+            }]
         });
 
-        output.dev_dependencies = json["dev-dependencies"].as_object().map(|v| {
-            v.iter()
-                // .map(|(k, v)| self.parse_dependency(k, v))
-                .filter_map(|(key, value)| {
-                    let dependency = self.parse_dependency(key, value);
+        let mut features = Feature::parse_all(&json["features"]);
 
-                    dependency.ok()
-                })
-                .collect()
-        });
+        // optional dependencies not referenced through `dep:` are implicit features named after them
+        // https://doc.rust-lang.org/cargo/reference/features.html#optional-dependencies
+        let referenced: Vec<String> = features
+            .iter()
+            .flat_map(|f| f.enables.iter())
+            .filter_map(|e| e.strip_prefix("dep:").map(|e| e.to_string()))
+            .collect();
+        let default_set: Vec<String> = json["features"]["default"]
+            .as_array()
+            .map(|d| {
+                d.iter()
+                    .filter_map(|f| f.as_str().map(|f| f.to_string()))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        for dependency in output.dependencies.clone().into_iter().flatten() {
+            // a renamed dependency is referenced by its key, not by the name of the package
+            let key = dependency.rename.unwrap_or(dependency.name);
+
+            if dependency.optional
+                && !referenced.contains(&key)
+                && !features.iter().any(|f| f.name == key)
+            {
+                features.push(Feature {
+                    enables: vec![format!("dep:{}", key)],
+                    default: default_set.contains(&key),
+                    name: key,
+                });
+            }
+        }
 
-        output.build_dependencies = json["build-dependencies"].as_object().map(|v| {
-            v.iter()
-                // .map(|(k, v)| self.parse_dependency(k, v))
-                .filter_map(|(key, value)| {
-                    let dependency = self.parse_dependency(key, value);
+        if !features.is_empty() {
+            output.features = Some(features);
+        }
 
-                    dependency.ok()
+        let targets = Target::parse_all(&file_path, &json);
+
+        // examples are a great way to show how to use the package
+        output.scripts = Some(
+            targets
+                .iter()
+                .filter(|t| t.kind == TargetKind::Example)
+                .map(|example| Script {
+                    name: example.name.clone(),
+                    command: example.run_command(),
+                    description: Some(format!("Runs the `{}` example", example.name)),
+                    source: file_path.clone(),
                 })
-                .collect()
-        });
+                .collect(),
+        );
+
+        if !targets.is_empty() {
+            output.targets = Some(targets);
+        }
+
+        // a workspace root runs the tests of every member only with --workspace
+        let command = match json["workspace"].is_object() {
+            true => "cargo test --workspace",
+            false => "cargo test",
+        };
+
+        output.test_commands = Some(vec![Script {
+            name: "test".to_string(),
+            command: command.to_string(),
+            description: None,
+            source: file_path.clone(),
+        }]);
 
         output.trim();
         Ok(output)
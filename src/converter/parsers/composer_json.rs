@@ -4,9 +4,12 @@ use anyhow::{anyhow, Error};
 use serde_json::Value;
 use strum::IntoEnumIterator;
 
-use crate::converter::{
-    Component, Contributor, ConverterOutput, Decorator, Dependency, Funding, FundingType, License,
-    Repository,
+use crate::{
+    converter::{
+        Component, Contributor, ConverterOutput, Decorator, Dependency, DependencySource, Funding,
+        FundingType, License, Repository,
+    },
+    elements::{script::Script, toolchain::Prerequisite},
 };
 
 /// The composer.json parser
@@ -27,7 +30,7 @@ impl Component for ComposerJson {
     fn convert(&self, file_path: String, file_contents: String) -> Result<ConverterOutput, Error> {
         let mut output = ConverterOutput::empty();
 
-        output.source_config_file_path = file_path;
+        output.source_config_file_path = file_path.clone();
 
         let json: Value = serde_json::from_str(file_contents.as_str()).unwrap();
 
@@ -106,6 +109,10 @@ impl Component for ComposerJson {
                 .collect()
         });
 
+        output.prerequisites = json["require"]["php"]
+            .as_str()
+            .map(|version| vec![Prerequisite::new("php", version, &file_path)]);
+
         output.dev_dependencies = json["require-dev"].as_object().map(|v| {
             v.iter()
                 // .map(|(k, v)| self.parse_dependency(k, v))
@@ -117,6 +124,48 @@ impl Component for ComposerJson {
                 .collect()
         });
 
+        // event scripts (eg. post-install-cmd) are run by composer itself, not by the user
+        let events = regex::Regex::new(r"^(pre|post)-").unwrap();
+
+        output.scripts = json["scripts"].as_object().map(|scripts| {
+            scripts
+                .iter()
+                .filter(|(name, _)| !events.is_match(name))
+                .map(|(name, body)| {
+                    let description = match body {
+                        Value::String(body) => Some(format!("`{}`", body)),
+                        Value::Array(bodies) => Some(
+                            bodies
+                                .iter()
+                                .filter_map(|b| b.as_str().map(|b| format!("`{}`", b)))
+                                .collect::<Vec<String>>()
+                                .join(", "),
+                        ),
+                        _ => None,
+                    };
+
+                    Script {
+                        name: name.to_string(),
+                        command: format!("composer run-script {}", name),
+                        // prefer the human description when the author provided one
+                        description: json["scripts-descriptions"][name]
+                            .as_str()
+                            .map(|d| d.to_string())
+                            .or(description),
+                        source: file_path.clone(),
+                    }
+                })
+                .collect()
+        });
+
+        output.test_commands = output.scripts.as_ref().map(|scripts| {
+            scripts
+                .iter()
+                .filter(|s| s.name == "test" || s.name.starts_with("test:"))
+                .cloned()
+                .collect()
+        });
+
         output.funding = json["funding"].as_array().map(|v| {
             v.iter()
                 .map(|f| self.parse_funding(f))
@@ -146,13 +195,38 @@ impl Component for ComposerJson {
 
         let url = attrs.get("homepage").map(|s| s.to_string());
 
-        Ok(Contributor { name, email, url })
+        Ok(Contributor {
+            name,
+            email,
+            url,
+            stats: None,
+        })
     }
 
+    /// Parses a package link, the platform packages (php, extensions and libraries) are not installed by composer
+    ///
+    /// Reference: https://getcomposer.org/doc/01-basic-usage.md#platform-packages
     fn parse_dependency(&self, key: &String, value: &Value) -> Result<Dependency, Error> {
+        let is_platform = key == "php"
+            || key.starts_with("php-")
+            || key.starts_with("ext-")
+            || key.starts_with("lib-")
+            || [
+                "composer",
+                "composer-plugin-api",
+                "composer-runtime-api",
+                "hhvm",
+            ]
+            .contains(&key.as_str());
+
         Ok(Dependency {
             name: key.to_string(),
-            version: Some(value.to_string()),
+            version: value.as_str().map(|v| v.to_string()),
+            source: match is_platform {
+                true => DependencySource::Platform,
+                false => DependencySource::Registry,
+            },
+            ..Dependency::default()
         })
     }
 
@@ -0,0 +1,165 @@
+use anyhow::{anyhow, Error};
+use itertools::Itertools;
+use serde_json::Value;
+
+use crate::{
+    converter::{Component, Contributor, ConverterOutput, Decorator, Dependency, Funding},
+    elements::docker::{Docker, DockerService},
+};
+
+/// The docker-compose.yml parser
+///
+/// Reference: https://docs.docker.com/compose/compose-file/
+pub struct DockerCompose {
+    // component: Rc<dyn Component>,
+}
+
+impl Decorator for DockerCompose {
+    fn new(/* component: Rc<dyn Component> */) -> Self {
+        DockerCompose {}
+    }
+}
+
+impl DockerCompose {
+    /// Returns the keys of a map or the items of a list, depends_on and environment support both forms
+    fn keys_or_items(value: &Value) -> Vec<String> {
+        match value {
+            Value::Object(map) => map.keys().cloned().collect(),
+            Value::Array(items) => items
+                .iter()
+                .filter_map(|item| item.as_str().map(|s| s.to_string()))
+                .collect(),
+            _ => vec![],
+        }
+    }
+
+    /// Finds the variables interpolated without a default value (`${VAR}`, `${VAR:?error}`) in the given value
+    fn interpolated_variables(value: &Value) -> Vec<String> {
+        let regex = regex::Regex::new(r"\$\{([A-Za-z_][A-Za-z0-9_]*)(:?\?[^}]*)?\}").unwrap();
+
+        match value {
+            Value::String(s) => regex
+                .captures_iter(s)
+                .filter_map(|c| c.get(1).map(|m| m.as_str().to_string()))
+                .collect(),
+            Value::Array(items) => items
+                .iter()
+                .flat_map(DockerCompose::interpolated_variables)
+                .collect(),
+            Value::Object(map) => map
+                .values()
+                .flat_map(DockerCompose::interpolated_variables)
+                .collect(),
+            _ => vec![],
+        }
+    }
+
+    /// Returns the environment variables the host has to provide: the ones declared without a value and the
+    /// ones interpolated without a default
+    fn required_env(service: &Value) -> Vec<String> {
+        let environment = &service["environment"];
+
+        let without_value: Vec<String> = match environment {
+            Value::Object(map) => map
+                .iter()
+                .filter(|(_, value)| value.is_null())
+                .map(|(key, _)| key.to_string())
+                .collect(),
+            Value::Array(_) => DockerCompose::keys_or_items(environment)
+                .into_iter()
+                .filter(|item| !item.contains('='))
+                .collect(),
+            _ => vec![],
+        };
+
+        without_value
+            .into_iter()
+            .chain(DockerCompose::interpolated_variables(service))
+            .unique()
+            .collect()
+    }
+
+    /// Parses the short (`"8080:80"`) and long (`{ target: 80, published: 8080 }`) syntax of a port
+    fn parse_port(port: &Value) -> Option<String> {
+        match port {
+            Value::String(s) => Some(s.to_string()),
+            Value::Number(n) => Some(n.to_string()),
+            Value::Object(_) => {
+                let target = match &port["target"] {
+                    Value::Null => return None,
+                    Value::String(s) => s.to_string(),
+                    target => target.to_string(),
+                };
+
+                match &port["published"] {
+                    Value::Null => Some(target),
+                    Value::String(s) => Some(format!("{}:{}", s, target)),
+                    published => Some(format!("{}:{}", published, target)),
+                }
+            }
+            _ => None,
+        }
+    }
+
+    fn parse_service(name: &str, service: &Value) -> DockerService {
+        DockerService {
+            name: name.to_string(),
+            image: service["image"].as_str().map(|s| s.to_string()),
+            ports: service["ports"]
+                .as_array()
+                .map(|ports| ports.iter().filter_map(DockerCompose::parse_port).collect())
+                .unwrap_or_default(),
+            required_env: DockerCompose::required_env(service),
+            depends_on: DockerCompose::keys_or_items(&service["depends_on"]),
+        }
+    }
+}
+
+impl Component for DockerCompose {
+    fn convert(&self, file_path: String, file_contents: String) -> Result<ConverterOutput, Error> {
+        let mut output = ConverterOutput::empty();
+
+        output.source_config_file_path = file_path.clone();
+
+        let yaml: Value = match serde_yaml::from_str(file_contents.as_str()) {
+            Ok(yaml) => yaml,
+            Err(e) => return Err(anyhow!("Could not parse docker-compose file: {}", e)),
+        };
+
+        if yaml["name"].as_str().is_some() && !yaml["name"].as_str().unwrap().is_empty() {
+            output.name = Some(yaml["name"].as_str().unwrap().to_string());
+        }
+
+        let services = match yaml["services"].as_object() {
+            Some(services) => services,
+            None => return Err(anyhow!("No services found in docker-compose file")),
+        };
+
+        let mut docker = Docker::empty();
+        docker.compose_file = Some(file_path);
+        docker.services = services
+            .iter()
+            .map(|(name, service)| DockerCompose::parse_service(name, service))
+            .collect();
+        output.docker = Some(docker);
+
+        output.trim();
+        Ok(output)
+    }
+
+    fn parse_contributor(&self, _contributor: &Value) -> Result<Contributor, Error> {
+        Err(anyhow!(
+            "Contributors are not supported for docker-compose.yml!"
+        ))
+    }
+
+    fn parse_dependency(&self, _key: &String, _value: &Value) -> Result<Dependency, Error> {
+        Err(anyhow!(
+            "Dependencies are not supported for docker-compose.yml!"
+        ))
+    }
+
+    fn parse_funding(&self, _funding: &Value) -> Result<Funding, Error> {
+        Err(anyhow!("Funding is not supported for docker-compose.yml!"))
+    }
+}
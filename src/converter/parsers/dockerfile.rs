@@ -0,0 +1,284 @@
+use std::collections::BTreeMap;
+
+use anyhow::{anyhow, Error};
+use serde_json::Value;
+
+use crate::{
+    converter::{
+        Component, Contributor, ConverterOutput, Decorator, Dependency, Funding, License,
+        Repository,
+    },
+    elements::docker::{Docker, DockerImage},
+};
+
+/// The prefix of the labels defined by the OCI image spec
+///
+/// Reference: https://github.com/opencontainers/image-spec/blob/main/annotations.md
+const OCI_LABEL_PREFIX: &str = "org.opencontainers.image.";
+
+/// A build stage of the Dockerfile, each FROM instruction starts a new one
+#[derive(Debug, Clone, Default)]
+struct Stage {
+    alias: Option<String>,
+    base_image: Option<String>,
+    exposed_ports: Vec<String>,
+    entrypoint: Option<String>,
+    cmd: Option<String>,
+    labels: BTreeMap<String, String>,
+}
+
+/// The Dockerfile parser
+///
+/// Reference: https://docs.docker.com/engine/reference/builder/
+pub struct Dockerfile {
+    // component: Rc<dyn Component>,
+}
+
+impl Decorator for Dockerfile {
+    fn new(/* component: Rc<dyn Component> */) -> Self {
+        Dockerfile {}
+    }
+}
+
+impl Dockerfile {
+    /// Joins the lines ending with a backslash and drops comments and empty lines
+    fn logical_lines(file_contents: &str) -> Vec<String> {
+        let mut lines: Vec<String> = vec![];
+        let mut current = String::new();
+
+        for line in file_contents.lines() {
+            let trimmed = line.trim();
+
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+
+            match trimmed.strip_suffix('\\') {
+                Some(continued) => {
+                    current.push_str(continued);
+                    current.push(' ');
+                }
+                None => {
+                    current.push_str(trimmed);
+                    lines.push(current.trim().to_string());
+                    current = String::new();
+                }
+            }
+        }
+
+        if !current.trim().is_empty() {
+            lines.push(current.trim().to_string());
+        }
+
+        lines
+    }
+
+    /// Splits a string by whitespace keeping double quoted values together and removing the quotes
+    fn split_words(args: &str) -> Vec<String> {
+        let mut words: Vec<String> = vec![];
+        let mut current = String::new();
+        let mut quoted = false;
+
+        for c in args.chars() {
+            match c {
+                '"' => quoted = !quoted,
+                c if c.is_whitespace() && !quoted => {
+                    if !current.is_empty() {
+                        words.push(current.clone());
+                        current.clear();
+                    }
+                }
+                c => current.push(c),
+            }
+        }
+
+        if !current.is_empty() {
+            words.push(current);
+        }
+
+        words
+    }
+
+    /// Parses the exec form (`["npm", "start"]`) or the shell form (`npm start`) of ENTRYPOINT and CMD
+    fn parse_command(args: &str) -> String {
+        match serde_json::from_str::<Vec<String>>(args) {
+            Ok(exec) => exec.join(" "),
+            Err(_) => args.to_string(),
+        }
+    }
+
+    /// Parses the `key=value` pairs of a LABEL instruction, the legacy `LABEL key value` form is supported too
+    fn parse_labels(args: &str) -> Vec<(String, String)> {
+        let words = Dockerfile::split_words(args);
+
+        if !words.is_empty() && !words[0].contains('=') {
+            return vec![(words[0].clone(), words[1..].join(" "))];
+        }
+
+        words
+            .iter()
+            .filter_map(|word| {
+                word.split_once('=')
+                    .map(|(key, value)| (key.to_string(), value.to_string()))
+            })
+            .collect()
+    }
+
+    /// Parses the instructions of the Dockerfile and returns the final stage
+    fn parse_stages(file_contents: &str) -> Option<Stage> {
+        let mut stages: Vec<Stage> = vec![];
+
+        for line in Dockerfile::logical_lines(file_contents) {
+            let (instruction, args) = match line.split_once(char::is_whitespace) {
+                Some((instruction, args)) => (instruction.to_uppercase(), args.trim()),
+                None => continue,
+            };
+
+            if instruction == "FROM" {
+                let words: Vec<String> = Dockerfile::split_words(args)
+                    .into_iter()
+                    .filter(|w| !w.starts_with("--"))
+                    .collect();
+
+                let image = words.first().cloned();
+                let alias = match words.get(1) {
+                    Some(keyword) if keyword.eq_ignore_ascii_case("as") => words.get(2).cloned(),
+                    _ => None,
+                };
+
+                // a stage built from a previous one inherits its configuration
+                let parent = stages
+                    .iter()
+                    .find(|s| s.alias.is_some() && s.alias == image)
+                    .cloned();
+
+                let stage = match parent {
+                    Some(parent) => Stage { alias, ..parent },
+                    None => Stage {
+                        alias,
+                        base_image: image,
+                        ..Stage::default()
+                    },
+                };
+
+                stages.push(stage);
+                continue;
+            }
+
+            let stage = match stages.last_mut() {
+                Some(stage) => stage,
+                // instructions before the first FROM (eg. ARG) do not belong to any stage
+                None => continue,
+            };
+
+            match instruction.as_str() {
+                "EXPOSE" => stage.exposed_ports.extend(Dockerfile::split_words(args)),
+                "ENTRYPOINT" => stage.entrypoint = Some(Dockerfile::parse_command(args)),
+                "CMD" => stage.cmd = Some(Dockerfile::parse_command(args)),
+                "LABEL" => {
+                    for (key, value) in Dockerfile::parse_labels(args) {
+                        if let Some(key) = key.strip_prefix(OCI_LABEL_PREFIX) {
+                            stage.labels.insert(key.to_string(), value);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        stages.pop()
+    }
+}
+
+impl Component for Dockerfile {
+    fn convert(&self, file_path: String, file_contents: String) -> Result<ConverterOutput, Error> {
+        let mut output = ConverterOutput::empty();
+
+        output.source_config_file_path = file_path.clone();
+
+        let stage = match Dockerfile::parse_stages(&file_contents) {
+            Some(stage) => stage,
+            None => return Err(anyhow!("Could not find any FROM instruction in Dockerfile")),
+        };
+
+        let labels = &stage.labels;
+        let label = |key: &str| labels.get(key).filter(|v| !v.is_empty()).cloned();
+
+        output.name = label("title");
+        output.description = label("description");
+        output.version = label("version");
+        output.homepage_url = label("url");
+        output.license = label("licenses").map(License::from_name);
+        output.repository = label("source").map(Repository::new);
+
+        output.contributors = label("authors").map(|authors| {
+            authors
+                .split(',')
+                .filter_map(|author| {
+                    let contributor = self.parse_contributor(&Value::from(author));
+
+                    contributor.ok()
+                })
+                .collect()
+        });
+
+        let mut docker = Docker::empty();
+        docker.image = Some(DockerImage {
+            path: file_path,
+            base_image: stage.base_image,
+            exposed_ports: stage.exposed_ports,
+            entrypoint: stage.entrypoint,
+            cmd: stage.cmd,
+            labels: stage.labels,
+        });
+        output.docker = Some(docker);
+
+        output.trim();
+        Ok(output)
+    }
+
+    /// Parses an author of the `org.opencontainers.image.authors` label in the `Name <email>` form
+    fn parse_contributor(&self, contributor: &Value) -> Result<Contributor, Error> {
+        let as_str = match contributor.as_str() {
+            Some(s) if !s.trim().is_empty() => s.trim(),
+            _ => {
+                return Err(anyhow!(
+                    "Could not parse contributor! Value: {}",
+                    contributor
+                ))
+            }
+        };
+
+        let regex = regex::Regex::new(r"^([^<]*?)\s*(?:<([^>]*)>)?$").unwrap();
+        let captures = match regex.captures(as_str) {
+            Some(captures) => captures,
+            None => {
+                return Err(anyhow!(
+                    "Could not parse contributor! Value: {}",
+                    contributor
+                ))
+            }
+        };
+
+        let name = captures
+            .get(1)
+            .map(|m| m.as_str().to_string())
+            .filter(|s| !s.is_empty());
+        let email = captures.get(2).map(|m| m.as_str().to_string());
+
+        Ok(Contributor {
+            name,
+            email,
+            url: None,
+            stats: None,
+        })
+    }
+
+    fn parse_dependency(&self, _key: &String, _value: &Value) -> Result<Dependency, Error> {
+        Err(anyhow!("Dependencies are not supported for Dockerfile!"))
+    }
+
+    fn parse_funding(&self, _funding: &Value) -> Result<Funding, Error> {
+        Err(anyhow!("Funding is not supported for Dockerfile!"))
+    }
+}
@@ -0,0 +1,107 @@
+use anyhow::{anyhow, Error};
+use serde_json::Value;
+
+use crate::{
+    converter::{Component, Contributor, ConverterOutput, Decorator, Dependency, Funding},
+    elements::script::Script,
+};
+
+/// The justfile parser, it lists the recipes which can be run with `just <recipe>`
+///
+/// The comment right above a recipe is used as its description, private recipes are skipped.
+///
+/// Reference: https://just.systems/man/en/
+pub struct Justfile {
+    // component: Rc<dyn Component>,
+}
+
+impl Decorator for Justfile {
+    fn new(/* component: Rc<dyn Component> */) -> Self {
+        Justfile {}
+    }
+}
+
+impl Component for Justfile {
+    fn convert(&self, file_path: String, file_contents: String) -> Result<ConverterOutput, Error> {
+        let mut output = ConverterOutput::empty();
+
+        output.source_config_file_path = file_path.clone();
+
+        let mut scripts: Vec<Script> = vec![];
+
+        // the doc comment and the attributes apply to the recipe declared right after them
+        let mut doc: Option<String> = None;
+        let mut private = false;
+
+        for line in file_contents.lines() {
+            let trimmed = line.trim();
+
+            if trimmed.is_empty() || line.starts_with(char::is_whitespace) {
+                doc = None;
+                private = false;
+                continue;
+            }
+
+            if trimmed.starts_with("#!") {
+                continue;
+            }
+
+            if let Some(comment) = trimmed.strip_prefix('#') {
+                doc = Some(comment.trim().to_string()).filter(|c| !c.is_empty());
+                continue;
+            }
+
+            if trimmed.starts_with('[') {
+                private = private || trimmed.contains("private");
+                continue;
+            }
+
+            let is_setting = ["set ", "alias ", "export ", "import ", "mod "]
+                .iter()
+                .any(|keyword| trimmed.starts_with(keyword));
+
+            let header = match trimmed.split_once(':') {
+                Some((header, rest)) if !is_setting && !rest.starts_with('=') => header,
+                _ => {
+                    doc = None;
+                    private = false;
+                    continue;
+                }
+            };
+
+            let name = header
+                .split_whitespace()
+                .next()
+                .unwrap_or("")
+                .trim_start_matches('@');
+
+            if !name.is_empty() && !name.starts_with('_') && !private {
+                scripts.push(Script {
+                    name: name.to_string(),
+                    command: format!("just {}", name),
+                    description: doc.clone(),
+                    source: file_path.clone(),
+                });
+            }
+
+            doc = None;
+            private = false;
+        }
+
+        output.scripts = Some(scripts);
+
+        Ok(output)
+    }
+
+    fn parse_contributor(&self, _contributor: &Value) -> Result<Contributor, Error> {
+        Err(anyhow!("Contributors are not supported for justfile!"))
+    }
+
+    fn parse_dependency(&self, _key: &String, _value: &Value) -> Result<Dependency, Error> {
+        Err(anyhow!("Dependencies are not supported for justfile!"))
+    }
+
+    fn parse_funding(&self, _funding: &Value) -> Result<Funding, Error> {
+        Err(anyhow!("Funding is not supported for justfile!"))
+    }
+}
@@ -0,0 +1,108 @@
+use anyhow::{anyhow, Error};
+use serde_json::Value;
+
+use crate::{
+    converter::{Component, Contributor, ConverterOutput, Decorator, Dependency, Funding},
+    elements::script::Script,
+};
+
+/// The Makefile parser, it lists the targets which can be run with `make <target>`
+///
+/// Targets can be documented with a `##` comment on the same line, e.g. `build: deps ## Builds the project`.
+/// When at least one target is documented, only the documented ones are listed.
+///
+/// Reference: https://www.gnu.org/software/make/manual/make.html#Rule-Syntax
+pub struct Makefile {
+    // component: Rc<dyn Component>,
+}
+
+impl Decorator for Makefile {
+    fn new(/* component: Rc<dyn Component> */) -> Self {
+        Makefile {}
+    }
+}
+
+impl Makefile {
+    /// Parses a rule line returning its targets and the optional `##` help comment
+    fn parse_rule(line: &str) -> Option<(Vec<String>, Option<String>)> {
+        let (targets, rest) = line.split_once(':')?;
+
+        // `:=` and `::=` are assignments, `=` before the colon is an assignment containing a colon
+        if rest.starts_with('=') || rest.starts_with(":=") || targets.contains('=') {
+            return None;
+        }
+
+        let targets: Vec<String> = targets
+            .split_whitespace()
+            // special (.PHONY), pattern (%.o) and computed ($(OBJ)) targets can't be typed by the user
+            .filter(|t| !t.starts_with('.') && !t.contains('%') && !t.contains('$'))
+            .map(|t| t.to_string())
+            .collect();
+
+        if targets.is_empty() {
+            return None;
+        }
+
+        let help = rest
+            .split_once("##")
+            .map(|(_, help)| help.trim().to_string())
+            .filter(|help| !help.is_empty());
+
+        Some((targets, help))
+    }
+}
+
+impl Component for Makefile {
+    fn convert(&self, file_path: String, file_contents: String) -> Result<ConverterOutput, Error> {
+        let mut output = ConverterOutput::empty();
+
+        output.source_config_file_path = file_path.clone();
+
+        let mut scripts: Vec<Script> = vec![];
+
+        for line in file_contents.lines() {
+            // recipe lines start with a tab, comments and directives can't declare targets
+            if line.starts_with(char::is_whitespace) || line.trim_start().starts_with('#') {
+                continue;
+            }
+
+            let (targets, help) = match Makefile::parse_rule(line) {
+                Some(rule) => rule,
+                None => continue,
+            };
+
+            for target in targets {
+                if scripts.iter().any(|s| s.name == target) {
+                    continue;
+                }
+
+                scripts.push(Script {
+                    command: format!("make {}", target),
+                    name: target,
+                    description: help.clone(),
+                    source: file_path.clone(),
+                });
+            }
+        }
+
+        if scripts.iter().any(|s| s.description.is_some()) {
+            scripts.retain(|s| s.description.is_some());
+        }
+
+        output.scripts = Some(scripts);
+
+        Ok(output)
+    }
+
+    fn parse_contributor(&self, _contributor: &Value) -> Result<Contributor, Error> {
+        Err(anyhow!("Contributors are not supported for Makefile!"))
+    }
+
+    fn parse_dependency(&self, _key: &String, _value: &Value) -> Result<Dependency, Error> {
+        Err(anyhow!("Dependencies are not supported for Makefile!"))
+    }
+
+    fn parse_funding(&self, _funding: &Value) -> Result<Funding, Error> {
+        Err(anyhow!("Funding is not supported for Makefile!"))
+    }
+}
@@ -6,10 +6,14 @@ use strum::IntoEnumIterator;
 
 use crate::{
     converter::{
-        Component, Contributor, Contributors, ConverterOutput, Decorator, Dependency, Funding,
-        FundingType, Fundings, Repository,
+        Component, Contributor, Contributors, ConverterOutput, Decorator, Dependency,
+        DependencySource, Funding, FundingType, Fundings, Repository,
+    },
+    elements::{
+        license::License,
+        script::{PackageManager, Script},
+        toolchain::Prerequisite,
     },
-    elements::license::License,
 };
 
 /// The package.json parser
@@ -30,7 +34,7 @@ impl Component for PackageJson {
     fn convert(&self, file_path: String, file_contents: String) -> Result<ConverterOutput, Error> {
         let mut output = ConverterOutput::empty();
 
-        output.source_config_file_path = file_path;
+        output.source_config_file_path = file_path.clone();
 
         let json: Value = serde_json::from_str(file_contents.as_str()).unwrap();
 
@@ -110,6 +114,32 @@ impl Component for PackageJson {
                 .collect()
         });
 
+        // optional dependencies are installed too, but the install doesn't fail when they can't be
+        let optional_dependencies: Vec<Dependency> = json["optionalDependencies"]
+            .as_object()
+            .map(|v| {
+                v.iter()
+                    .filter_map(|(key, value)| self.parse_dependency(key, value).ok())
+                    .map(|dependency| Dependency {
+                        optional: true,
+                        ..dependency
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        if !optional_dependencies.is_empty() {
+            output.dependencies = Some(
+                output
+                    .dependencies
+                    .take()
+                    .into_iter()
+                    .flatten()
+                    .chain(optional_dependencies)
+                    .collect(),
+            );
+        }
+
         output.dev_dependencies = json["devDependencies"].as_object().map(|v| {
             v.iter()
                 // .map(|(k, v)| self.parse_dependency(k, v))
@@ -121,6 +151,46 @@ impl Component for PackageJson {
                 .collect()
         });
 
+        // engines are ranges (eg. >=18), the exact versions come from the pin files (eg. .nvmrc)
+        output.prerequisites = json["engines"].as_object().map(|engines| {
+            engines
+                .iter()
+                .filter_map(|(tool, version)| {
+                    version
+                        .as_str()
+                        .map(|version| Prerequisite::new(tool, version, &file_path))
+                })
+                .collect()
+        });
+
+        let package_manager = PackageManager::detect(&file_path, &json);
+
+        output.scripts = json["scripts"].as_object().map(|scripts| {
+            scripts
+                .iter()
+                .map(|(name, body)| Script {
+                    name: name.to_string(),
+                    command: package_manager.run(name),
+                    description: body.as_str().map(|body| format!("`{}`", body)),
+                    source: file_path.clone(),
+                })
+                .collect()
+        });
+
+        // `npm init` creates a test script failing on purpose, it doesn't run any test
+        output.test_commands = output.scripts.as_ref().map(|scripts| {
+            scripts
+                .iter()
+                .filter(|s| s.name == "test" || s.name.starts_with("test:"))
+                .filter(|s| {
+                    !s.description
+                        .as_ref()
+                        .is_some_and(|d| d.contains("no test specified"))
+                })
+                .cloned()
+                .collect()
+        });
+
         if json["funding"].is_array() {
             output.funding = json["funding"].as_array().map(|v| {
                 v.iter()
@@ -153,17 +223,111 @@ impl Component for PackageJson {
         let attrs = as_obj.unwrap();
 
         Ok(Contributor {
-            name: attrs["name"].as_str().map(|s| s.to_string()),
-            email: attrs["email"].as_str().map(|s| s.to_string()),
-            url: attrs["url"].as_str().map(|s| s.to_string()),
+            name: attrs
+                .get("name")
+                .and_then(|s| s.as_str())
+                .map(|s| s.to_string()),
+            email: attrs
+                .get("email")
+                .and_then(|s| s.as_str())
+                .map(|s| s.to_string()),
+            url: attrs
+                .get("url")
+                .and_then(|s| s.as_str())
+                .map(|s| s.to_string()),
+            stats: None,
         })
     }
 
+    /// Parses the dependency specifier which can be a version range, a git url, a local path, a tarball url
+    /// a workspace reference or an alias
+    ///
+    /// Reference: https://docs.npmjs.com/cli/v9/configuring-npm/package-json#dependencies
     fn parse_dependency(&self, key: &String, value: &Value) -> Result<Dependency, Error> {
-        Ok(Dependency {
-            name: key.to_string(),
-            version: Some(value.to_string()),
-        })
+        let spec = match value.as_str() {
+            Some(spec) => spec.trim(),
+            None => {
+                return Err(anyhow!(
+                    "Could not parse dependency! Key: {}, Value: {}",
+                    key,
+                    value
+                ))
+            }
+        };
+
+        let registry = |name: &str, version: &str| Dependency {
+            name: name.to_string(),
+            version: Some(version.to_string()).filter(|v| !v.is_empty()),
+            ..Dependency::default()
+        };
+
+        // the github shorthand `user/repo#ref`
+        let github_shorthand = regex::Regex::new(r"^[^./@:][^/:]*/[^/:]+$").unwrap();
+
+        let dependency = if let Some(version) = spec.strip_prefix("workspace:") {
+            Dependency {
+                source: DependencySource::Workspace,
+                ..registry(key, version)
+            }
+        } else if let Some(path) = spec
+            .strip_prefix("file:")
+            .or_else(|| spec.strip_prefix("link:"))
+        {
+            Dependency {
+                name: key.to_string(),
+                source: DependencySource::Path(path.to_string()),
+                ..Dependency::default()
+            }
+        } else if let Some(alias) = spec.strip_prefix("npm:") {
+            // the alias `npm:real-name@range` installs real-name under the key, scoped names start with @
+            match alias.rfind('@').filter(|i| *i > 0) {
+                Some(i) => registry(&alias[..i], &alias[i + 1..]),
+                None => registry(alias, ""),
+            }
+        } else if [
+            "git+",
+            "git://",
+            "git@",
+            "github:",
+            "gitlab:",
+            "bitbucket:",
+            "gist:",
+        ]
+        .iter()
+        .any(|p| spec.starts_with(p))
+            || github_shorthand.is_match(spec)
+        {
+            let (url, reference) = match spec.split_once('#') {
+                Some((url, reference)) => (url, Some(reference.to_string())),
+                None => (spec, None),
+            };
+
+            // expand the hosted shortcuts to browsable urls
+            let url = match url.split_once(':') {
+                Some(("github", repo)) => format!("https://github.com/{}", repo),
+                Some(("gitlab", repo)) => format!("https://gitlab.com/{}", repo),
+                Some(("bitbucket", repo)) => format!("https://bitbucket.org/{}", repo),
+                Some(("gist", id)) => format!("https://gist.github.com/{}", id),
+                None => format!("https://github.com/{}", url),
+                _ => url.trim_start_matches("git+").to_string(),
+            };
+
+            Dependency {
+                name: key.to_string(),
+                source: DependencySource::Git { url, reference },
+                ..Dependency::default()
+            }
+        } else if spec.starts_with("http://") || spec.starts_with("https://") {
+            Dependency {
+                name: key.to_string(),
+                source: DependencySource::Url(spec.to_string()),
+                ..Dependency::default()
+            }
+        } else {
+            registry(key, spec)
+        };
+
+        Ok(dependency)
     }
 
     fn parse_funding(&self, funding: &Value) -> Result<Funding, Error> {
@@ -0,0 +1,67 @@
+use anyhow::{anyhow, Error};
+use serde_json::Value;
+
+use crate::{
+    converter::{Component, Contributor, ConverterOutput, Decorator, Dependency, Funding},
+    elements::script::Script,
+};
+
+/// The Taskfile.yml parser, it lists the tasks which can be run with `task <name>`
+///
+/// Reference: https://taskfile.dev/api/#schema
+pub struct TaskfileYml {
+    // component: Rc<dyn Component>,
+}
+
+impl Decorator for TaskfileYml {
+    fn new(/* component: Rc<dyn Component> */) -> Self {
+        TaskfileYml {}
+    }
+}
+
+impl Component for TaskfileYml {
+    fn convert(&self, file_path: String, file_contents: String) -> Result<ConverterOutput, Error> {
+        let mut output = ConverterOutput::empty();
+
+        output.source_config_file_path = file_path.clone();
+
+        let yaml: Value = match serde_yaml::from_str(file_contents.as_str()) {
+            Ok(yaml) => yaml,
+            Err(e) => return Err(anyhow!("Could not parse Taskfile: {}", e)),
+        };
+
+        output.scripts = yaml["tasks"].as_object().map(|tasks| {
+            tasks
+                .iter()
+                // internal tasks can't be called from the command line
+                .filter(|(_, task)| !task["internal"].as_bool().unwrap_or(false))
+                .map(|(name, task)| Script {
+                    name: name.to_string(),
+                    command: match name.as_str() {
+                        "default" => "task".to_string(),
+                        _ => format!("task {}", name),
+                    },
+                    description: task["desc"]
+                        .as_str()
+                        .or(task["summary"].as_str())
+                        .map(|s| s.trim().to_string()),
+                    source: file_path.clone(),
+                })
+                .collect()
+        });
+
+        Ok(output)
+    }
+
+    fn parse_contributor(&self, _contributor: &Value) -> Result<Contributor, Error> {
+        Err(anyhow!("Contributors are not supported for Taskfile.yml!"))
+    }
+
+    fn parse_dependency(&self, _key: &String, _value: &Value) -> Result<Dependency, Error> {
+        Err(anyhow!("Dependencies are not supported for Taskfile.yml!"))
+    }
+
+    fn parse_funding(&self, _funding: &Value) -> Result<Funding, Error> {
+        Err(anyhow!("Funding is not supported for Taskfile.yml!"))
+    }
+}
@@ -0,0 +1,209 @@
+use std::{fmt::Display, fs, path::Path};
+
+use serde::Serialize;
+use serde_json::Value;
+
+/// A feature declared in the `[features]` table of a Cargo.toml
+///
+/// Reference: https://doc.rust-lang.org/cargo/reference/features.html
+#[derive(Debug, Clone, Serialize)]
+pub struct Feature {
+    pub name: String,
+    /// The features and dependencies enabled by this feature, e.g. `dep:serde` or `tokio/rt`
+    pub enables: Vec<String>,
+    /// Whether the feature is part of the default set
+    pub default: bool,
+}
+
+impl Feature {
+    /// Parses the `[features]` table, the `default` entry is not a feature by itself but marks the default set
+    pub fn parse_all(features: &Value) -> Vec<Feature> {
+        let features = match features.as_object() {
+            Some(features) => features,
+            None => return vec![],
+        };
+
+        let default: Vec<&str> = features
+            .get("default")
+            .and_then(|d| d.as_array())
+            .map(|d| d.iter().filter_map(|f| f.as_str()).collect())
+            .unwrap_or_default();
+
+        features
+            .iter()
+            .filter(|(name, _)| name.as_str() != "default")
+            .map(|(name, enables)| Feature {
+                name: name.to_string(),
+                enables: enables
+                    .as_array()
+                    .map(|e| {
+                        e.iter()
+                            .filter_map(|f| f.as_str().map(|f| f.to_string()))
+                            .collect()
+                    })
+                    .unwrap_or_default(),
+                default: default.contains(&name.as_str()),
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+pub enum TargetKind {
+    Bin,
+    Example,
+}
+
+impl Display for TargetKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let kind = match self {
+            TargetKind::Bin => "bin",
+            TargetKind::Example => "example",
+        };
+
+        write!(f, "{}", kind)
+    }
+}
+
+/// A binary or an example of a Cargo package, declared in the Cargo.toml or discovered from the package layout
+///
+/// Reference: https://doc.rust-lang.org/cargo/reference/cargo-targets.html
+#[derive(Debug, Clone, Serialize)]
+pub struct Target {
+    pub name: String,
+    pub kind: TargetKind,
+    /// The name of the package the target belongs to
+    pub package: String,
+    /// The features needed to build the target
+    pub required_features: Vec<String>,
+    /// Whether the package can be published on crates.io (`publish = false` is not)
+    pub published: bool,
+}
+
+impl Target {
+    /// Returns the binaries and the examples of the package, the ones declared through `[[bin]]` and `[[example]]`
+    /// come first followed by the ones discovered automatically from the package layout
+    pub fn parse_all(manifest_path: &str, manifest: &Value) -> Vec<Target> {
+        let package = match manifest["package"]["name"].as_str() {
+            Some(package) => package.to_string(),
+            None => return vec![],
+        };
+
+        let published = manifest["package"]["publish"].as_bool().unwrap_or(true)
+            && manifest["package"]["publish"]
+                .as_array()
+                .is_none_or(|r| !r.is_empty());
+
+        let root = Path::new(manifest_path).parent().unwrap_or(Path::new(""));
+
+        let mut targets: Vec<Target> = vec![];
+
+        for (kind, table, auto, dir) in [
+            (TargetKind::Bin, "bin", "autobins", "src/bin"),
+            (TargetKind::Example, "example", "autoexamples", "examples"),
+        ] {
+            let declared = manifest[table].as_array().cloned().unwrap_or_default();
+
+            for target in declared {
+                if let Some(name) = target["name"].as_str() {
+                    targets.push(Target {
+                        name: name.to_string(),
+                        kind,
+                        package: package.clone(),
+                        required_features: target["required-features"]
+                            .as_array()
+                            .map(|f| {
+                                f.iter()
+                                    .filter_map(|f| f.as_str().map(|f| f.to_string()))
+                                    .collect()
+                            })
+                            .unwrap_or_default(),
+                        published,
+                    });
+                }
+            }
+
+            if !manifest["package"][auto].as_bool().unwrap_or(true) {
+                continue;
+            }
+
+            let mut discovered = Target::discover(&root.join(dir));
+            if kind == TargetKind::Bin && root.join("src/main.rs").exists() {
+                discovered.insert(0, package.clone());
+            }
+
+            for name in discovered {
+                if targets.iter().any(|t| t.kind == kind && t.name == name) {
+                    continue;
+                }
+
+                targets.push(Target {
+                    name,
+                    kind,
+                    package: package.clone(),
+                    required_features: vec![],
+                    published,
+                });
+            }
+        }
+
+        targets
+    }
+
+    /// Lists the target names found in a directory, both `name.rs` files and `name/main.rs` folders are targets
+    fn discover(dir: &Path) -> Vec<String> {
+        let entries = match fs::read_dir(dir) {
+            Ok(entries) => entries,
+            Err(_) => return vec![],
+        };
+
+        let mut names: Vec<String> = entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter_map(|path| {
+                if path.is_file() && path.extension().is_some_and(|e| e == "rs") {
+                    path.file_stem().map(|s| s.to_string_lossy().to_string())
+                } else if path.is_dir() && path.join("main.rs").exists() {
+                    path.file_name().map(|s| s.to_string_lossy().to_string())
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        names.sort();
+        names
+    }
+
+    /// Returns the command installing the binary, a single binary package doesn't need the `--bin` flag
+    pub fn install_command(&self, repository_url: Option<&String>, single: bool) -> String {
+        let mut command = match (self.published, repository_url) {
+            (true, _) => format!("cargo install {}", self.package),
+            (false, Some(url)) if !url.is_empty() => {
+                format!("cargo install --git {} {}", url, self.package)
+            }
+            (false, _) => "cargo install --path .".to_string(),
+        };
+
+        if !single {
+            command.push_str(&format!(" --bin {}", self.name));
+        }
+
+        if !self.required_features.is_empty() {
+            command.push_str(&format!(" --features {}", self.required_features.join(",")));
+        }
+
+        command
+    }
+
+    /// Returns the command running the example
+    pub fn run_command(&self) -> String {
+        let mut command = format!("cargo run --example {}", self.name);
+
+        if !self.required_features.is_empty() {
+            command.push_str(&format!(" --features {}", self.required_features.join(",")));
+        }
+
+        command
+    }
+}
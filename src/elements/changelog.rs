@@ -0,0 +1,275 @@
+use std::collections::HashMap;
+
+use anyhow::Error;
+use git2::{Oid, Sort};
+use serde::Serialize;
+
+use crate::{converter::ConverterOutput, elements::repository::Repository};
+
+/// The commit types of the Conventional Commits convention with the title of their changelog section, in the order
+/// the sections are listed
+///
+/// Reference: https://www.conventionalcommits.org/en/v1.0.0/
+pub const COMMIT_TYPES: [(&str, &str); 11] = [
+    ("feat", "Features"),
+    ("fix", "Bug Fixes"),
+    ("perf", "Performance Improvements"),
+    ("revert", "Reverts"),
+    ("refactor", "Code Refactoring"),
+    ("docs", "Documentation"),
+    ("style", "Styles"),
+    ("test", "Tests"),
+    ("build", "Build System"),
+    ("ci", "Continuous Integration"),
+    ("chore", "Chores"),
+];
+
+/// The first line of a conventional commit: its type, its optional scope, the `!` of a breaking change and its subject
+const HEADER: &str = r"^(\w+)(?:\(([^)]*)\))?(!)?: (.+)$";
+
+/// A commit whose message follows the Conventional Commits convention, e.g. `feat(parser)!: drop the v1 format`
+#[derive(Debug, Clone, Serialize)]
+pub struct Commit {
+    pub hash: String,
+    /// The type of the change, e.g. feat, fix
+    pub kind: String,
+    pub scope: Option<String>,
+    pub subject: String,
+    /// The description of the breaking change, the subject is used when the commit is marked with `!` only
+    pub breaking: Option<String>,
+}
+
+/// The commits released under a tag, the ones not tagged yet belong to the unreleased changes
+#[derive(Debug, Clone, Serialize)]
+pub struct Release {
+    /// The tag of the release, `None` for the unreleased changes
+    pub version: Option<String>,
+    /// The day the tagged commit was made, in the YYYY-MM-DD format
+    pub date: Option<String>,
+    /// The tag of the release before this one, used to link the changes between the two
+    pub previous: Option<String>,
+    pub commits: Vec<Commit>,
+}
+
+impl Commit {
+    /// Parses the message of a commit with the [`HEADER`] regex, `None` is returned when the message doesn't follow
+    /// the convention
+    ///
+    /// ```text
+    /// feat(api)!: remove the v1 endpoints
+    ///
+    /// BREAKING CHANGE: the clients must move to the v2 endpoints
+    /// ```
+    pub fn parse(header: &regex::Regex, hash: &str, message: &str) -> Option<Commit> {
+        let mut lines = message.lines();
+        let captures = header.captures(lines.next()?.trim())?;
+
+        let kind = captures[1].to_lowercase();
+        if !COMMIT_TYPES.iter().any(|(t, _)| *t == kind) {
+            return None;
+        }
+
+        let subject = captures[4].trim().to_string();
+
+        // the footer may span several lines, it ends with the paragraph
+        let mut breaking: Option<String> = None;
+        for line in lines {
+            match breaking.as_mut() {
+                Some(description) if !line.trim().is_empty() => {
+                    description.push(' ');
+                    description.push_str(line.trim());
+                }
+                Some(_) => break,
+                None => {
+                    breaking = ["BREAKING CHANGE:", "BREAKING-CHANGE:"]
+                        .iter()
+                        .find_map(|footer| line.strip_prefix(footer))
+                        .map(|description| description.trim().to_string());
+                }
+            }
+        }
+
+        if breaking.is_none() && captures.get(3).is_some() {
+            breaking = Some(subject.clone());
+        }
+
+        Some(Commit {
+            hash: hash.to_string(),
+            kind,
+            scope: captures
+                .get(2)
+                .map(|s| s.as_str().trim().to_string())
+                .filter(|s| !s.is_empty()),
+            subject,
+            breaking,
+        })
+    }
+
+    /// Returns the abbreviated hash of the commit, as shown by git
+    pub fn short_hash(&self) -> &str {
+        &self.hash[..7.min(self.hash.len())]
+    }
+}
+
+impl Release {
+    /// Walks the history of the repository release by release, from the head back to the first tag reachable from
+    /// it, and keeps the conventional commits of each one, the most recent release comes first
+    pub fn scan(project_location: &str) -> Result<ConverterOutput, Error> {
+        let repo = Repository::discover(project_location)?;
+        let head = repo.head()?.peel_to_commit()?;
+        let header = regex::Regex::new(HEADER).unwrap();
+
+        // a commit may be tagged more than once, e.g. v1.0.0 and latest
+        let mut tags: HashMap<Oid, Vec<String>> = HashMap::new();
+        for name in repo.tag_names(None)?.iter().flatten() {
+            let commit = match repo
+                .revparse_single(&format!("refs/tags/{}", name))
+                .and_then(|object| object.peel_to_commit())
+            {
+                Ok(commit) => commit,
+                Err(_) => continue,
+            };
+
+            // the tags of other branches aren't releases of this history
+            if commit.id() != head.id() && !repo.graph_descendant_of(head.id(), commit.id())? {
+                continue;
+            }
+
+            tags.entry(commit.id()).or_default().push(name.to_string());
+        }
+
+        let mut tagged: Vec<(Oid, String, git2::Time)> = tags
+            .iter()
+            .map(|(oid, names)| {
+                let version = names
+                    .iter()
+                    .find(|name| name.trim_start_matches('v').starts_with(char::is_numeric))
+                    .unwrap_or(&names[0]);
+
+                Ok((*oid, version.to_string(), repo.find_commit(*oid)?.time()))
+            })
+            .collect::<Result<_, Error>>()?;
+        tagged.sort_by(|a, b| {
+            b.2.seconds()
+                .cmp(&a.2.seconds())
+                .then_with(|| b.1.cmp(&a.1))
+        });
+
+        // each release goes from its tag to the one before, the unreleased changes go from the head to the last tag
+        let mut releases: Vec<Release> = vec![];
+        for (i, tag) in std::iter::once(None)
+            .chain(tagged.iter().map(Some))
+            .enumerate()
+        {
+            let previous = tagged.get(i);
+
+            let mut revwalk = repo.revwalk()?;
+            revwalk.set_sorting(Sort::TOPOLOGICAL | Sort::TIME)?;
+            revwalk.push(tag.map_or(head.id(), |(oid, _, _)| *oid))?;
+            if let Some((oid, _, _)) = previous {
+                revwalk.hide(*oid)?;
+            }
+
+            let mut commits: Vec<Commit> = vec![];
+            for oid in revwalk {
+                let commit = repo.find_commit(oid?)?;
+
+                // a merge commit adds nothing, the commits it merges are walked too
+                if commit.parent_count() > 1 {
+                    continue;
+                }
+
+                if let Some(parsed) = Commit::parse(
+                    &header,
+                    &commit.id().to_string(),
+                    commit.message().unwrap_or(""),
+                ) {
+                    commits.push(parsed);
+                }
+            }
+
+            if commits.is_empty() {
+                continue;
+            }
+
+            releases.push(Release {
+                version: tag.map(|(_, version, _)| version.clone()),
+                date: tag.and_then(|(_, _, time)| {
+                    chrono::NaiveDateTime::from_timestamp_opt(time.seconds(), 0)
+                        .map(|date| date.format("%Y-%m-%d").to_string())
+                }),
+                previous: previous.map(|(_, version, _)| version.clone()),
+                commits,
+            });
+        }
+
+        let mut converter = ConverterOutput::empty();
+        converter.source_config_file_path = Repository::git_dir(&repo);
+        converter.releases = Some(releases).filter(|r| !r.is_empty());
+
+        Ok(converter)
+    }
+
+    /// Returns the heading of the release as it appears in a changelog, e.g. `1.2.0` or `Unreleased`
+    pub fn title(&self) -> String {
+        self.version.clone().unwrap_or("Unreleased".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(message: &str) -> Option<Commit> {
+        Commit::parse(
+            &regex::Regex::new(HEADER).unwrap(),
+            "0123456789abcdef",
+            message,
+        )
+    }
+
+    #[test]
+    fn parses_the_header() {
+        let commit = parse("feat(parser): read the v2 format").unwrap();
+
+        assert_eq!(commit.kind, "feat");
+        assert_eq!(commit.scope.as_deref(), Some("parser"));
+        assert_eq!(commit.subject, "read the v2 format");
+        assert!(commit.breaking.is_none());
+        assert_eq!(commit.short_hash(), "0123456");
+    }
+
+    #[test]
+    fn marks_the_breaking_changes_with_the_subject() {
+        let commit = parse("feat(api)!: remove the v1 endpoints").unwrap();
+
+        assert_eq!(commit.breaking.as_deref(), Some("remove the v1 endpoints"));
+    }
+
+    #[test]
+    fn reads_the_breaking_change_footer_over_several_lines() {
+        let commit = parse(
+            "feat!: remove the v1 endpoints\n\nthe body\n\nBREAKING CHANGE: the clients must move\nto the v2 endpoints\n\nRefs: #12",
+        )
+        .unwrap();
+
+        assert_eq!(
+            commit.breaking.as_deref(),
+            Some("the clients must move to the v2 endpoints")
+        );
+    }
+
+    #[test]
+    fn ignores_the_unknown_types() {
+        assert!(parse("wip: half of the parser").is_none());
+        assert!(parse("Merge branch 'main'").is_none());
+    }
+
+    #[test]
+    fn drops_an_empty_scope() {
+        let commit = parse("fix( ): handle the empty files").unwrap();
+
+        assert!(commit.scope.is_none());
+        assert_eq!(commit.subject, "handle the empty files");
+    }
+}
@@ -0,0 +1,305 @@
+use std::{fmt::Display, fs, path::Path};
+
+use anyhow::Error;
+use handlebars::Handlebars;
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use crate::{
+    converter::ConverterOutput,
+    elements::repository::{Repository, RepositoryPlatform},
+    utils::{paths, GenMarkdown, Shield},
+};
+
+/// The continuous integration systems whose configuration is recognized
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum CiSystem {
+    GithubActions,
+    GitlabCi,
+    CircleCi,
+    AzurePipelines,
+}
+
+impl Display for CiSystem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            CiSystem::GithubActions => "GitHub Actions",
+            CiSystem::GitlabCi => "GitLab CI",
+            CiSystem::CircleCi => "CircleCI",
+            CiSystem::AzurePipelines => "Azure Pipelines",
+        };
+
+        write!(f, "{}", name)
+    }
+}
+
+/// A pipeline declared in the CI configuration of the project, e.g. a GitHub Actions workflow
+#[derive(Debug, Clone, Serialize)]
+pub struct Workflow {
+    pub name: String,
+    pub system: CiSystem,
+    /// The location of the configuration file in the project structure
+    pub file: String,
+    /// The branches whose pushes or pull requests trigger the workflow, `None` when every branch does
+    pub branches: Option<Vec<String>>,
+}
+
+/// A status badge served by the CI system itself
+#[derive(Debug, Clone, Serialize)]
+pub struct Badge {
+    pub src: String,
+    pub alt_text: String,
+    pub target: String,
+}
+
+impl GenMarkdown for Badge {
+    fn gen_md(&self) -> Result<String, Error> {
+        let badge_tpl = paths::read_util_file_contents(paths::UtilityPath::BadgeReadme);
+        let mut handlebars = Handlebars::new();
+        handlebars
+            .register_template_string("badge_tpl", badge_tpl)
+            .unwrap();
+
+        Ok(handlebars.render("badge_tpl", &json!(self)).unwrap())
+    }
+}
+
+impl Workflow {
+    /// Scan the project for CI configuration files and return a list of converter output
+    /// each converter output will contain the workflows of one configuration file
+    pub fn scan(paths: &Vec<String>) -> Result<Vec<ConverterOutput>, Error> {
+        let github_workflow = regex::Regex::new(r"\.github/workflows/[^/]+\.ya?ml$").unwrap();
+
+        let mut converter_outputs: Vec<ConverterOutput> = vec![];
+
+        for path in paths {
+            let system = if github_workflow.is_match(path) {
+                CiSystem::GithubActions
+            } else if path.ends_with(".gitlab-ci.yml") {
+                CiSystem::GitlabCi
+            } else if path.ends_with(".circleci/config.yml") {
+                CiSystem::CircleCi
+            } else if path.ends_with("azure-pipelines.yml") {
+                CiSystem::AzurePipelines
+            } else {
+                continue;
+            };
+
+            let yaml: Value = match fs::read_to_string(path)
+                .map_err(Error::new)
+                .and_then(|c| serde_yaml::from_str(&c).map_err(Error::new))
+            {
+                Ok(yaml) => yaml,
+                Err(_) => continue,
+            };
+
+            let mut converter = ConverterOutput::empty();
+            converter.source_config_file_path = path.to_string();
+            converter.workflows = Some(Workflow::parse(system, &yaml, path));
+
+            converter_outputs.push(converter);
+        }
+
+        Ok(converter_outputs)
+    }
+
+    /// Returns the workflows declared in the given configuration file
+    fn parse(system: CiSystem, yaml: &Value, path: &str) -> Vec<Workflow> {
+        let file_stem = Path::new(path)
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        match system {
+            // Reference: https://docs.github.com/en/actions/using-workflows/workflow-syntax-for-github-actions#on
+            CiSystem::GithubActions => vec![Workflow {
+                name: yaml["name"].as_str().unwrap_or(&file_stem).to_string(),
+                system,
+                file: path.to_string(),
+                branches: Workflow::github_branches(&yaml["on"]),
+            }],
+            CiSystem::GitlabCi => vec![Workflow {
+                name: system.to_string(),
+                system,
+                file: path.to_string(),
+                branches: None,
+            }],
+            // Reference: https://circleci.com/docs/configuration-reference/#workflows
+            CiSystem::CircleCi => {
+                let workflows: Vec<Workflow> = yaml["workflows"]
+                    .as_object()
+                    .map(|workflows| {
+                        workflows
+                            .keys()
+                            .filter(|name| name.as_str() != "version")
+                            .map(|name| Workflow {
+                                name: name.to_string(),
+                                system,
+                                file: path.to_string(),
+                                branches: None,
+                            })
+                            .collect()
+                    })
+                    .unwrap_or_default();
+
+                if workflows.is_empty() {
+                    return vec![Workflow {
+                        name: system.to_string(),
+                        system,
+                        file: path.to_string(),
+                        branches: None,
+                    }];
+                }
+
+                workflows
+            }
+            // Reference: https://learn.microsoft.com/en-us/azure/devops/pipelines/yaml-schema/trigger
+            CiSystem::AzurePipelines => vec![Workflow {
+                name: yaml["name"]
+                    .as_str()
+                    // the name may be the run number format, e.g. $(Date:yyyyMMdd)$(Rev:.r)
+                    .filter(|name| !name.contains("$("))
+                    .unwrap_or(&system.to_string())
+                    .to_string(),
+                system,
+                file: path.to_string(),
+                branches: Workflow::azure_branches(&yaml["trigger"], &yaml["pr"]),
+            }],
+        }
+    }
+
+    /// Returns the branches triggering a GitHub Actions workflow through push and pull request events
+    fn github_branches(on: &Value) -> Option<Vec<String>> {
+        let events = ["push", "pull_request", "pull_request_target"];
+
+        match on {
+            Value::String(event) if events.contains(&event.as_str()) => None,
+            Value::Array(on)
+                if on
+                    .iter()
+                    .any(|e| events.contains(&e.as_str().unwrap_or(""))) =>
+            {
+                None
+            }
+            Value::Object(on) => {
+                let mut branches: Vec<String> = vec![];
+
+                for (event, filters) in on.iter().filter(|(e, _)| events.contains(&e.as_str())) {
+                    match filters["branches"].as_array() {
+                        Some(filter) => branches
+                            .extend(filter.iter().filter_map(|b| b.as_str()).map(String::from)),
+                        // a push filtered by tags only doesn't run for branches
+                        None if event == "push" && filters.get("tags").is_some() => {}
+                        None => return None,
+                    }
+                }
+
+                Some(branches)
+            }
+            _ => Some(vec![]),
+        }
+    }
+
+    /// Returns the branches triggering an Azure pipeline, every branch does when no trigger is declared
+    fn azure_branches(trigger: &Value, pr: &Value) -> Option<Vec<String>> {
+        if trigger.is_null() {
+            return None;
+        }
+
+        let mut branches: Vec<String> = vec![];
+
+        for trigger in [trigger, pr] {
+            let included = match trigger {
+                Value::Array(branches) => branches.clone(),
+                Value::Object(_) => match trigger["branches"]["include"].as_array() {
+                    Some(branches) => branches.clone(),
+                    None => return None,
+                },
+                // `none` disables the trigger
+                _ => vec![],
+            };
+
+            branches.extend(included.iter().filter_map(|b| b.as_str()).map(String::from));
+        }
+
+        Some(branches)
+    }
+
+    /// Whether the workflow checks the changes pushed or proposed to the given branch
+    pub fn protects(&self, branch: &str) -> bool {
+        let branches = match &self.branches {
+            Some(branches) => branches,
+            None => return true,
+        };
+
+        branches.iter().any(|pattern| {
+            pattern == branch
+                || pattern
+                    .strip_suffix('*')
+                    .is_some_and(|prefix| branch.starts_with(prefix.trim_end_matches('*')))
+        })
+    }
+
+    /// Returns the status badge of the workflow for the given branch, a static shield is used when the system
+    /// doesn't serve badges for the repository platform
+    pub fn badge(&self, repository: &Repository, branch: &str) -> Result<String, Error> {
+        let sign = repository.sign.clone().unwrap_or_default();
+        let file_name = Path::new(&self.file)
+            .file_name()
+            .map(|f| f.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let alt_text = format!("{} status", self.name);
+
+        let badge = match (self.system, &repository.platform) {
+            (_, _) if sign.is_empty() => None,
+            (CiSystem::GithubActions, RepositoryPlatform::Github) => Some(Badge {
+                src: format!(
+                    "https://github.com/{}/actions/workflows/{}/badge.svg?branch={}",
+                    sign, file_name, branch
+                ),
+                alt_text,
+                target: format!(
+                    "https://github.com/{}/actions/workflows/{}",
+                    sign, file_name
+                ),
+            }),
+            (CiSystem::GitlabCi, RepositoryPlatform::Gitlab) => Some(Badge {
+                src: format!("{}/badges/{}/pipeline.svg", repository.url, branch),
+                alt_text,
+                target: format!("{}/-/pipelines", repository.url),
+            }),
+            (CiSystem::CircleCi, RepositoryPlatform::Github | RepositoryPlatform::Bitbucket) => {
+                let vcs = match repository.platform {
+                    RepositoryPlatform::Github => "gh",
+                    _ => "bb",
+                };
+
+                Some(Badge {
+                    src: format!(
+                        "https://dl.circleci.com/status-badge/img/{}/{}/tree/{}.svg",
+                        vcs, sign, branch
+                    ),
+                    alt_text,
+                    target: format!(
+                        "https://dl.circleci.com/status-badge/redirect/{}/{}/tree/{}",
+                        vcs, sign, branch
+                    ),
+                })
+            }
+            _ => None,
+        };
+
+        match badge {
+            Some(badge) => badge.gen_md(),
+            None => Shield::new(
+                "CI",
+                &self.name,
+                "informational",
+                "",
+                &format!("{} on {}", self.name, self.system),
+                &repository.url,
+            )
+            .gen_md(),
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use std::{collections::BTreeMap, fmt::Display, path::Path};
+
+use serde::Serialize;
+
+/// The image built from a Dockerfile found in the project
+#[derive(Debug, Clone, Serialize)]
+pub struct DockerImage {
+    /// The location of the Dockerfile in the project structure
+    pub path: String,
+    /// The image the final stage is built from, e.g. `node:18-alpine`
+    pub base_image: Option<String>,
+    /// The ports declared through `EXPOSE`, e.g. `8080/tcp`
+    pub exposed_ports: Vec<String>,
+    pub entrypoint: Option<String>,
+    pub cmd: Option<String>,
+    /// The `org.opencontainers.image.*` labels, keyed without the prefix (eg. `title`, `source`)
+    pub labels: BTreeMap<String, String>,
+}
+
+impl DockerImage {
+    /// Returns the exposed ports without the protocol, ready to be published with `docker run -p`
+    pub fn ports(&self) -> Vec<String> {
+        self.exposed_ports
+            .iter()
+            .map(|port| port.split('/').next().unwrap_or(port).to_string())
+            .collect()
+    }
+}
+
+impl Display for DockerImage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            self.base_image.as_ref().unwrap_or(&"None".to_string())
+        )
+    }
+}
+
+/// A service declared in a docker-compose file
+#[derive(Debug, Clone, Serialize)]
+pub struct DockerService {
+    pub name: String,
+    /// The image of the service, missing when the service is built from a Dockerfile
+    pub image: Option<String>,
+    /// The ports published by the service in the `host:container` form
+    pub ports: Vec<String>,
+    /// The environment variables that must be provided by the host for the service to start
+    pub required_env: Vec<String>,
+    pub depends_on: Vec<String>,
+}
+
+/// The docker related information of the project
+#[derive(Debug, Clone, Serialize)]
+pub struct Docker {
+    pub image: Option<DockerImage>,
+    /// The location of the docker-compose file in the project structure
+    pub compose_file: Option<String>,
+    pub services: Vec<DockerService>,
+}
+
+/// Displays the compose services so the user can tell the compose files apart while merging
+impl Display for Docker {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let services: Vec<&str> = self.services.iter().map(|s| s.name.as_str()).collect();
+
+        write!(f, "services: {}", services.join(", "))
+    }
+}
+
+impl Docker {
+    /// Creates a new empty docker object
+    pub fn empty() -> Self {
+        Docker {
+            image: None,
+            compose_file: None,
+            services: vec![],
+        }
+    }
+
+    /// Returns a copy of the docker object with every path relative to the project location
+    pub fn relative_to(&self, project_location: &str) -> Self {
+        let relative = |path: &String| -> String {
+            Path::new(path)
+                .strip_prefix(project_location)
+                .map(|p| p.to_string_lossy().to_string())
+                .unwrap_or(path.to_string())
+        };
+
+        let mut docker = self.clone();
+
+        if let Some(image) = docker.image.as_mut() {
+            image.path = relative(&image.path);
+        }
+        docker.compose_file = docker.compose_file.as_ref().map(relative);
+
+        docker
+    }
+}
@@ -0,0 +1,121 @@
+use std::{fs, path::Path};
+
+use anyhow::Error;
+use serde::Serialize;
+
+use crate::converter::ConverterOutput;
+
+/// The templates of the env files, the `.env` itself is never read as it holds the actual secrets
+const ENV_TEMPLATES: [&str; 3] = [".env.example", ".env.sample", ".env.dist"];
+
+/// An environment variable documented in an env template (eg. `.env.example`)
+#[derive(Debug, Clone, Serialize)]
+pub struct EnvVar {
+    pub name: String,
+    /// The value assigned in the template, a variable without one must be set by the user
+    pub default: Option<String>,
+    /// The comments right above the variable
+    pub description: Option<String>,
+    /// The location of the env template in the project structure
+    pub source: String,
+}
+
+impl EnvVar {
+    /// Whether the user must provide a value for the variable
+    pub fn required(&self) -> bool {
+        self.default.is_none()
+    }
+
+    /// Scan the project for env templates and return a list of converter output
+    /// each converter output will contain the variables of one template
+    pub fn scan(paths: &Vec<String>) -> Result<Vec<ConverterOutput>, Error> {
+        let mut converter_outputs: Vec<ConverterOutput> = vec![];
+
+        for path in paths {
+            let is_template = Path::new(path)
+                .file_name()
+                .and_then(|f| f.to_str())
+                .is_some_and(|f| ENV_TEMPLATES.contains(&f));
+
+            if !is_template {
+                continue;
+            }
+
+            let contents = match fs::read_to_string(path) {
+                Ok(contents) => contents,
+                Err(_) => continue,
+            };
+
+            let mut converter = ConverterOutput::empty();
+            converter.source_config_file_path = path.to_string();
+            converter.env_vars = Some(EnvVar::parse(&contents, path));
+
+            converter_outputs.push(converter);
+        }
+
+        Ok(converter_outputs)
+    }
+
+    /// Parses the variables of an env file, the comments right above a variable describe it while a blank line
+    /// ends the description (eg. a section title)
+    ///
+    /// ```text
+    /// # The connection string of the database
+    /// DATABASE_URL=
+    /// export PORT=3000 # inline comments are dropped
+    /// ```
+    fn parse(contents: &str, path: &str) -> Vec<EnvVar> {
+        let assignment =
+            regex::Regex::new(r"^(?:export\s+)?([A-Za-z_][A-Za-z0-9_]*)\s*=(.*)$").unwrap();
+
+        let mut env_vars: Vec<EnvVar> = vec![];
+        let mut comments: Vec<String> = vec![];
+
+        for line in contents.lines() {
+            let line = line.trim();
+
+            if line.is_empty() {
+                comments.clear();
+                continue;
+            }
+
+            if let Some(comment) = line.strip_prefix('#') {
+                let comment = comment.trim();
+
+                // a commented out variable is an example, not a description
+                if assignment.is_match(comment) {
+                    comments.clear();
+                } else if !comment.is_empty() {
+                    comments.push(comment.to_string());
+                }
+                continue;
+            }
+
+            let captures = match assignment.captures(line) {
+                Some(captures) => captures,
+                None => continue,
+            };
+
+            let value = captures[2].trim();
+            let value = match value.chars().next() {
+                Some(quote @ ('"' | '\'')) => {
+                    value[1..].split(quote).next().unwrap_or("").to_string()
+                }
+                // a value made of a comment only, e.g. `PORT= # the port`, is empty
+                Some('#') => String::new(),
+                _ => value.split(" #").next().unwrap_or("").trim().to_string(),
+            };
+
+            env_vars.push(EnvVar {
+                name: captures[1].to_string(),
+                default: Some(value).filter(|v| !v.is_empty()),
+                description: Some(comments.join(" ")).filter(|d| !d.is_empty()),
+                source: path.to_string(),
+            });
+
+            comments.clear();
+        }
+
+        env_vars
+    }
+}
@@ -0,0 +1,195 @@
+use std::{collections::HashMap, fs, path::Path};
+
+use anyhow::{anyhow, Error};
+use serde::{Deserialize, Serialize};
+
+use crate::{converter::ConverterOutput, utils::paths};
+
+/// The directories holding third party code, they don't tell what the project is written in
+///
+/// Reference: https://github.com/github-linguist/linguist/blob/master/lib/linguist/vendor.yml
+const VENDORED_DIRS: [&str; 8] = [
+    "vendor",
+    "vendors",
+    "node_modules",
+    "third_party",
+    "third-party",
+    "bower_components",
+    "Pods",
+    ".yarn",
+];
+
+/// The file name fragments of the files produced by a tool rather than written by hand
+///
+/// Reference: https://github.com/github-linguist/linguist/blob/master/lib/linguist/generated.rb
+const GENERATED_FILES: [&str; 7] = [
+    ".min.js",
+    ".min.css",
+    ".pb.go",
+    "_pb2.py",
+    ".generated.",
+    ".g.dart",
+    ".designer.cs",
+];
+
+/// A language as listed in the languages catalog
+#[derive(Debug, Clone, Deserialize)]
+struct LanguageDefinition {
+    extensions: Vec<String>,
+    color: String,
+    logo: String,
+}
+
+/// The share of the source code of the project written in a language
+#[derive(Debug, Clone, Serialize)]
+pub struct Language {
+    pub name: String,
+    pub bytes: u64,
+    /// The share of the source code bytes, from 0 to 100
+    pub percentage: f64,
+    pub color: String,
+    pub logo: String,
+}
+
+impl Language {
+    /// Computes the languages breakdown of the project from the size of its source files, sorted by share
+    ///
+    /// The vendored and generated files are left out, as well as the ones marked as `linguist-vendored` or
+    /// `linguist-generated` in the .gitattributes file
+    pub fn scan(paths: &Vec<String>, project_location: &str) -> Result<ConverterOutput, Error> {
+        let contents: String = paths::read_util_file_contents(paths::UtilityPath::Languages);
+        let definitions: HashMap<String, LanguageDefinition> = match serde_yaml::from_str(&contents)
+        {
+            Ok(definitions) => definitions,
+            Err(_) => return Err(anyhow!("Error parsing languages file")),
+        };
+
+        let by_extension: HashMap<&str, &str> = definitions
+            .iter()
+            .flat_map(|(name, definition)| {
+                definition
+                    .extensions
+                    .iter()
+                    .map(move |extension| (extension.as_str(), name.as_str()))
+            })
+            .collect();
+
+        let excluded = Language::gitattributes_exclusions(project_location);
+
+        let mut bytes: HashMap<&str, u64> = HashMap::new();
+
+        for path in paths {
+            let relative = Path::new(path)
+                .strip_prefix(project_location)
+                .unwrap_or(Path::new(path));
+            let relative_str = relative.to_string_lossy();
+
+            let vendored = relative
+                .components()
+                .any(|c| VENDORED_DIRS.contains(&c.as_os_str().to_string_lossy().as_ref()));
+            let generated = GENERATED_FILES.iter().any(|g| relative_str.contains(g));
+
+            if vendored || generated || excluded.iter().any(|e| e.is_match(&relative_str)) {
+                continue;
+            }
+
+            let language = match relative
+                .extension()
+                .and_then(|e| by_extension.get(e.to_string_lossy().as_ref()))
+            {
+                Some(language) => language,
+                None => continue,
+            };
+
+            // directories are listed too, the metadata tells them apart
+            match fs::metadata(path) {
+                Ok(metadata) if metadata.is_file() => {
+                    *bytes.entry(language).or_insert(0) += metadata.len();
+                }
+                _ => continue,
+            }
+        }
+
+        let total: u64 = bytes.values().sum();
+
+        let mut languages: Vec<Language> = bytes
+            .into_iter()
+            .filter(|(_, bytes)| *bytes > 0)
+            .map(|(name, bytes)| Language {
+                name: name.to_string(),
+                bytes,
+                percentage: (bytes as f64 * 1000.0 / total as f64).round() / 10.0,
+                color: definitions[name].color.clone(),
+                logo: definitions[name].logo.clone(),
+            })
+            .collect();
+
+        languages.sort_by(|a, b| b.bytes.cmp(&a.bytes).then(a.name.cmp(&b.name)));
+
+        let mut converter = ConverterOutput::empty();
+        converter.source_config_file_path = project_location.to_string();
+        converter.languages = Some(languages).filter(|l| !l.is_empty());
+
+        Ok(converter)
+    }
+
+    /// Returns the patterns of the files marked as vendored or generated in the .gitattributes file, e.g.
+    ///
+    /// ```text
+    /// assets/js/* linguist-vendored
+    /// *.gen.ts linguist-generated=true
+    /// ```
+    fn gitattributes_exclusions(project_location: &str) -> Vec<regex::Regex> {
+        let contents = match fs::read_to_string(Path::new(project_location).join(".gitattributes"))
+        {
+            Ok(contents) => contents,
+            Err(_) => return vec![],
+        };
+
+        contents
+            .lines()
+            .filter_map(|line| {
+                let mut parts = line.split_whitespace();
+                let pattern = parts.next().filter(|p| !p.starts_with('#'))?;
+
+                let excluded = parts.any(|attribute| {
+                    ["linguist-vendored", "linguist-generated"]
+                        .iter()
+                        .any(|a| attribute == *a || attribute == format!("{}=true", a))
+                });
+
+                if !excluded {
+                    return None;
+                }
+
+                Language::glob_to_regex(pattern)
+            })
+            .collect()
+    }
+
+    /// Converts a gitattributes pattern to a regex, a pattern without a slash matches at any depth
+    fn glob_to_regex(pattern: &str) -> Option<regex::Regex> {
+        let anchored = pattern.trim_end_matches('/').contains('/');
+        let pattern = pattern.trim_start_matches('/');
+
+        let mut regex = String::from(if anchored { "^" } else { "(^|/)" });
+        let mut chars = pattern.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '*' if chars.peek() == Some(&'*') => {
+                    chars.next();
+                    regex.push_str(".*");
+                }
+                '*' => regex.push_str("[^/]*"),
+                '?' => regex.push_str("[^/]"),
+                c => regex.push_str(&regex::escape(&c.to_string())),
+            }
+        }
+
+        // a directory pattern excludes everything inside it
+        regex.push_str("(/.*)?$");
+
+        regex::Regex::new(&regex).ok()
+    }
+}
@@ -1,10 +1,11 @@
-use std::{collections::HashMap, fmt::Display, fs, io::Write, str::FromStr};
+use std::{collections::HashMap, fmt::Display, fs, io::Write, path::Path, str::FromStr};
 
 use anyhow::anyhow;
 use anyhow::Error;
 use chrono::{self, Datelike};
 use enum_assoc::Assoc;
 use handlebars::Handlebars;
+use itertools::Itertools;
 use serde_json::json;
 use std::fs::File;
 use strum::EnumIter;
@@ -14,41 +15,172 @@ use crate::{
     converter::ConverterOutput,
     utils::{
         paths::{self, read_util_file_contents, UtilityPath},
-        GenMarkdown,
+        trim, GenMarkdown,
     },
 };
 
 #[derive(Debug, Clone, PartialEq, EnumIter, Copy, Eq, Hash, Assoc)]
 #[func(pub const fn keywords(&self) -> &str)]
-/// The available licenses for a project which a user can choose from
+#[func(pub const fn id(&self) -> &str)]
+#[func(pub const fn file_suffix(&self) -> &str)]
+/// The available licenses for a project which a user can choose from, identified by their SPDX identifier
+///
+/// The file suffix names the license file when the project has more than one, e.g. `LICENSE-MIT`. The licenses are
+/// matched in order, so the more specific keywords come first (eg. LGPL before GPL)
+///
+/// Reference: https://spdx.org/licenses/
 pub enum SupportedLicense {
     #[assoc(keywords = "unknown")]
+    #[assoc(id = "Unknown")]
+    #[assoc(file_suffix = "")]
     Unknown,
-    #[assoc(keywords = "apache2, apache-2.0, apache-2, apache2.0, apache")]
+    #[assoc(keywords = "apache-2.0, apache2, apache-2, apache2.0, apache 2.0, apache")]
+    #[assoc(id = "Apache-2.0")]
+    #[assoc(file_suffix = "APACHE")]
     Apache20,
     #[assoc(keywords = "mit, mit license")]
+    #[assoc(id = "MIT")]
+    #[assoc(file_suffix = "MIT")]
     MIT,
-    #[assoc(keywords = "gnu general public license, gnu gpl, gpl")]
-    GNUGeneralPublicLicense,
-
+    #[assoc(keywords = "eupl-1.2, eupl 1.2, eupl, european union public licence")]
+    #[assoc(id = "EUPL-1.2")]
+    #[assoc(file_suffix = "EUPL")]
+    EUPL12,
+    #[assoc(
+        keywords = "mpl-2.0, mpl 2.0, mpl2, mozilla public license 2.0, mozilla public license"
+    )]
+    #[assoc(id = "MPL-2.0")]
+    #[assoc(file_suffix = "MPL")]
+    MPL20,
+    #[assoc(keywords = "bsd-2-clause, bsd 2-clause, simplified bsd, freebsd license")]
+    #[assoc(id = "BSD-2-Clause")]
+    #[assoc(file_suffix = "BSD")]
+    BSD2Clause,
+    #[assoc(keywords = "bsd-3-clause, bsd 3-clause, new bsd, revised bsd, modified bsd, bsd")]
+    #[assoc(id = "BSD-3-Clause")]
+    #[assoc(file_suffix = "BSD")]
+    BSD3Clause,
+    #[assoc(keywords = "0bsd, zero-clause bsd, bsd zero clause")]
+    #[assoc(id = "0BSD")]
+    #[assoc(file_suffix = "0BSD")]
+    ZeroBSD,
+    #[assoc(keywords = "isc, isc license")]
+    #[assoc(id = "ISC")]
+    #[assoc(file_suffix = "ISC")]
+    Isc,
+    #[assoc(
+        keywords = "agpl-3.0-only, agpl-3.0, agplv3, agpl v3, gnu affero general public license v3.0, gnu affero general public license v3"
+    )]
+    #[assoc(id = "AGPL-3.0-only")]
+    #[assoc(file_suffix = "AGPL")]
+    AGPL30Only,
+    #[assoc(
+        keywords = "agpl-3.0-or-later, agpl-3.0+, agplv3+, agpl, gnu affero general public license"
+    )]
+    #[assoc(id = "AGPL-3.0-or-later")]
+    #[assoc(file_suffix = "AGPL")]
+    AGPL30OrLater,
+    #[assoc(
+        keywords = "lgpl-2.1-only, lgpl-2.1, lgplv2.1, lgpl v2.1, gnu lesser general public license v2.1"
+    )]
+    #[assoc(id = "LGPL-2.1-only")]
+    #[assoc(file_suffix = "LGPL")]
+    LGPL21Only,
+    #[assoc(keywords = "lgpl-2.1-or-later, lgpl-2.1+, lgplv2.1+, lgplv2+")]
+    #[assoc(id = "LGPL-2.1-or-later")]
+    #[assoc(file_suffix = "LGPL")]
+    LGPL21OrLater,
+    #[assoc(
+        keywords = "lgpl-3.0-only, lgpl-3.0, lgplv3, lgpl v3, gnu lesser general public license v3.0, gnu lesser general public license v3"
+    )]
+    #[assoc(id = "LGPL-3.0-only")]
+    #[assoc(file_suffix = "LGPL")]
+    LGPL30Only,
+    #[assoc(
+        keywords = "lgpl-3.0-or-later, lgpl-3.0+, lgplv3+, lgpl, gnu lgpl, gnu lesser general public license"
+    )]
+    #[assoc(id = "LGPL-3.0-or-later")]
+    #[assoc(file_suffix = "LGPL")]
+    LGPL30OrLater,
+    #[assoc(
+        keywords = "gpl-2.0-only, gpl-2.0, gplv2, gpl v2, gnu general public license v2.0, gnu general public license v2"
+    )]
+    #[assoc(id = "GPL-2.0-only")]
+    #[assoc(file_suffix = "GPL")]
+    GPL20Only,
+    #[assoc(keywords = "gpl-2.0-or-later, gpl-2.0+, gplv2+")]
+    #[assoc(id = "GPL-2.0-or-later")]
+    #[assoc(file_suffix = "GPL")]
+    GPL20OrLater,
+    #[assoc(
+        keywords = "gpl-3.0-only, gpl-3.0, gplv3, gpl v3, gnu general public license v3.0, gnu general public license v3"
+    )]
+    #[assoc(id = "GPL-3.0-only")]
+    #[assoc(file_suffix = "GPL")]
+    GPL30Only,
     #[assoc(
-        keywords = "gnu lesser general public license, Attribution-ShareAlike 4.0, cc-by-sa-4.0, cc-by-sa"
+        keywords = "gpl-3.0-or-later, gpl-3.0+, gplv3+, gpl, gnu gpl, gnu general public license"
     )]
+    #[assoc(id = "GPL-3.0-or-later")]
+    #[assoc(file_suffix = "GPL")]
+    GPL30OrLater,
+    #[assoc(keywords = "unlicense, the unlicense")]
+    #[assoc(id = "Unlicense")]
+    #[assoc(file_suffix = "UNLICENSE")]
+    Unlicense,
+    #[assoc(keywords = "zlib, zlib license")]
+    #[assoc(id = "Zlib")]
+    #[assoc(file_suffix = "ZLIB")]
+    Zlib,
+    #[assoc(keywords = "bsl-1.0, bsl 1.0, boost software license")]
+    #[assoc(id = "BSL-1.0")]
+    #[assoc(file_suffix = "BSL")]
+    BSL10,
+    #[assoc(keywords = "cc-by-sa-4.0, cc-by-sa, attribution-sharealike 4.0")]
+    #[assoc(id = "CC-BY-SA-4.0")]
+    #[assoc(file_suffix = "CC-BY-SA")]
     CreativeCommonsAttributionShareAlike40,
 }
 
-impl ToString for SupportedLicense {
-    fn to_string(&self) -> String {
-        match self {
-            SupportedLicense::Unknown => "Unknown",
-            SupportedLicense::Apache20 => "Apache-2.0",
-            SupportedLicense::MIT => "MIT",
-            SupportedLicense::GNUGeneralPublicLicense => "GNU General Public License",
+impl SupportedLicense {
+    /// Returns the template of the license text, the `year` and the copyright `holder` are filled in when the
+    /// license has a copyright notice
+    fn template(&self) -> Option<String> {
+        let path = match self {
+            SupportedLicense::Apache20 => UtilityPath::Apache20,
+            SupportedLicense::MIT => UtilityPath::MIT,
+            SupportedLicense::EUPL12 => UtilityPath::EUPL12,
+            SupportedLicense::MPL20 => UtilityPath::MPL20,
+            SupportedLicense::BSD2Clause => UtilityPath::BSD2Clause,
+            SupportedLicense::BSD3Clause => UtilityPath::BSD3Clause,
+            SupportedLicense::ZeroBSD => UtilityPath::ZeroBSD,
+            SupportedLicense::Isc => UtilityPath::Isc,
+            SupportedLicense::AGPL30Only => UtilityPath::AGPL30,
+            SupportedLicense::AGPL30OrLater => UtilityPath::AGPL30,
+            SupportedLicense::LGPL21Only => UtilityPath::LGPL21,
+            SupportedLicense::LGPL21OrLater => UtilityPath::LGPL21,
+            SupportedLicense::LGPL30Only => UtilityPath::LGPL30,
+            SupportedLicense::LGPL30OrLater => UtilityPath::LGPL30,
+            SupportedLicense::GPL20Only => UtilityPath::GPL20,
+            SupportedLicense::GPL20OrLater => UtilityPath::GPL20,
+            SupportedLicense::GPL30Only => UtilityPath::GPL30,
+            SupportedLicense::GPL30OrLater => UtilityPath::GPL30,
+            SupportedLicense::Unlicense => UtilityPath::Unlicense,
+            SupportedLicense::Zlib => UtilityPath::Zlib,
+            SupportedLicense::BSL10 => UtilityPath::BSL10,
             SupportedLicense::CreativeCommonsAttributionShareAlike40 => {
-                "Creative Commons Attribution-ShareAlike 4.0"
+                UtilityPath::CreativeCommonsAttributionShareAlike40
             }
-        }
-        .to_string()
+            SupportedLicense::Unknown => return None,
+        };
+
+        Some(read_util_file_contents(path))
+    }
+}
+
+impl ToString for SupportedLicense {
+    fn to_string(&self) -> String {
+        self.id().to_string()
     }
 }
 
@@ -60,14 +192,12 @@ impl FromStr for SupportedLicense {
         let s = s.to_lowercase();
 
         let found = SupportedLicense::iter().find(|option| {
-            // regex is case insensitive and considers only whole words, example "mit" will not match emit
+            // regex is case insensitive and considers only whole identifiers, example "mit" will not match emit and
+            // "gpl-2.0" will not match gpl-2.0-or-later
             let options: Vec<String> = option
                 .keywords()
-                .split(",")
-                .map(|f| f.trim())
-                .collect::<Vec<&str>>()
-                .iter()
-                .map(|c| format!(r"(?i)\b{}\b", c))
+                .split(',')
+                .map(|c| format!(r"(?i)(?:^|[^\w+-]){}(?:$|[^\w+-])", regex::escape(c.trim())))
                 .collect();
 
             let regex_set = match regex::RegexSet::new(options) {
@@ -85,69 +215,503 @@ impl FromStr for SupportedLicense {
     }
 }
 
+/// The share of the text of a license a file must hold, and of the file explained by it, for the license to be
+/// recognized without asking the user
+const MIN_CONFIDENCE: f32 = 0.9;
+
+/// The share of the text of a license a file must hold to be taken for a license at all, the user isn't asked about
+/// the files below it
+const MIN_COVERAGE: f32 = 0.5;
+
+/// The pairs of words a license must have that another one hasn't to be told apart in a file holding both
+const MIN_OWN_BIGRAMS: usize = 10;
+
 #[derive(Debug, Clone, PartialEq)]
 /// The license object and related information
 pub struct License {
-    /// The license name
+    /// The license name, the first license of the expression when the project has more than one
     pub name: SupportedLicense,
+    /// The exception granted to the license, e.g. `GPL-2.0 WITH Classpath-exception-2.0`
+    pub exception: Option<String>,
     /// The location in the project structure
     pub path: Option<String>,
     /// The location on the web (github repository for example)
     pub url: Option<String>,
+    /// The licenses the project is published under when there are more than one, e.g. `MIT OR Apache-2.0`, each
+    /// one with its own file
+    pub expression: Option<Box<LicenseExpression>>,
+    /// How well the license file matches the text of the license, from 0 to 1, only for the licenses read from a file
+    pub confidence: Option<f32>,
+    /// The other licenses the license file matches as well, only for the licenses read from a file
+    pub matches: Vec<SupportedLicense>,
 }
 
-impl License {
-    /// Create a new License object from a name (string) base on the SupportedLicense methods
+/// An SPDX license expression, the licenses are combined by the `OR`, `AND` and `WITH` operators
+///
+/// Reference: https://spdx.github.io/spdx-spec/v2.3/SPDX-license-expressions/
+#[derive(Debug, Clone, PartialEq)]
+pub enum LicenseExpression {
+    License(License),
+    /// All the licenses apply
+    And(Vec<LicenseExpression>),
+    /// Any of the licenses applies, at the choice of the user
+    Or(Vec<LicenseExpression>),
+}
+
+impl LicenseExpression {
+    /// Parses an SPDX license expression, e.g. `(MIT OR Apache-2.0) AND GPL-2.0 WITH Classpath-exception-2.0`
     ///
-    /// If the name of the license is not recognized, the license will be set to `SupportedLicense::Unknown`
-    pub fn from_name(name: String) -> Self {
-        let license = match SupportedLicense::from_str(&name) {
-            Ok(license) => license,
-            Err(_) => {
-                return Self {
-                    name: SupportedLicense::Unknown,
-                    path: None,
-                    url: None,
-                }
+    /// The operators are case sensitive as in the specification, `AND` takes precedence over `OR`. Returns `None`
+    /// when the expression is malformed
+    pub fn parse(expression: &str) -> Option<LicenseExpression> {
+        let expression = expression.replace('(', " ( ").replace(')', " ) ");
+        let tokens: Vec<&str> = expression.split_whitespace().collect();
+
+        let mut position = 0;
+        let parsed = LicenseExpression::parse_or(&tokens, &mut position)?;
+
+        (position == tokens.len()).then_some(parsed)
+    }
+
+    fn parse_or(tokens: &[&str], position: &mut usize) -> Option<LicenseExpression> {
+        let mut operands = vec![LicenseExpression::parse_and(tokens, position)?];
+
+        while tokens.get(*position) == Some(&"OR") {
+            *position += 1;
+            operands.push(LicenseExpression::parse_and(tokens, position)?);
+        }
+
+        match operands.len() {
+            1 => operands.pop(),
+            _ => Some(LicenseExpression::Or(operands)),
+        }
+    }
+
+    fn parse_and(tokens: &[&str], position: &mut usize) -> Option<LicenseExpression> {
+        let mut operands = vec![LicenseExpression::parse_with(tokens, position)?];
+
+        while tokens.get(*position) == Some(&"AND") {
+            *position += 1;
+            operands.push(LicenseExpression::parse_with(tokens, position)?);
+        }
+
+        match operands.len() {
+            1 => operands.pop(),
+            _ => Some(LicenseExpression::And(operands)),
+        }
+    }
+
+    fn parse_with(tokens: &[&str], position: &mut usize) -> Option<LicenseExpression> {
+        if tokens.get(*position) == Some(&"(") {
+            *position += 1;
+            let inner = LicenseExpression::parse_or(tokens, position)?;
+
+            if tokens.get(*position) != Some(&")") {
+                return None;
             }
+            *position += 1;
+
+            return Some(inner);
+        }
+
+        // the license names found in the wild are not always identifiers, e.g. "GNU General Public License"
+        let start = *position;
+        while tokens
+            .get(*position)
+            .is_some_and(|t| !["OR", "AND", "WITH", "(", ")"].contains(t))
+        {
+            *position += 1;
+        }
+
+        if start == *position {
+            return None;
+        }
+
+        let mut license = License::from_identifier(&tokens[start..*position].join(" "));
+
+        if tokens.get(*position) == Some(&"WITH") {
+            license.exception = Some(tokens.get(*position + 1)?.to_string());
+            *position += 2;
+        }
+
+        Some(LicenseExpression::License(license))
+    }
+
+    /// Returns the expression as text with the operands sorted, the same expression written in another order (eg.
+    /// `MIT OR Apache-2.0` and `Apache-2.0 OR MIT`) has the same key
+    pub fn key(&self) -> String {
+        let (operands, operator) = match self {
+            LicenseExpression::License(license) => return license.to_string(),
+            LicenseExpression::And(operands) => (operands, " AND "),
+            LicenseExpression::Or(operands) => (operands, " OR "),
+        };
+
+        operands
+            .iter()
+            .map(|o| match o {
+                LicenseExpression::License(_) => o.key(),
+                _ => format!("({})", o.key()),
+            })
+            .sorted()
+            .join(operator)
+    }
+
+    /// Returns the licenses of the expression, in the order they appear
+    pub fn licenses(&self) -> Vec<&License> {
+        match self {
+            LicenseExpression::License(license) => vec![license],
+            LicenseExpression::And(operands) | LicenseExpression::Or(operands) => {
+                operands.iter().flat_map(|o| o.licenses()).collect()
+            }
+        }
+    }
+
+    /// Returns the licenses of the expression, in the order they appear
+    pub fn licenses_mut(&mut self) -> Vec<&mut License> {
+        match self {
+            LicenseExpression::License(license) => vec![license],
+            LicenseExpression::And(operands) | LicenseExpression::Or(operands) => {
+                operands.iter_mut().flat_map(|o| o.licenses_mut()).collect()
+            }
+        }
+    }
+
+    /// Returns the markdown of the expression, the licenses are linked to their files
+    fn gen_md(&self, nested: bool) -> Result<String, Error> {
+        let (operands, operator) = match self {
+            LicenseExpression::License(license) => return license.gen_md(),
+            LicenseExpression::And(operands) => (operands, " and "),
+            LicenseExpression::Or(operands) => (operands, " or "),
+        };
+
+        let md = operands
+            .iter()
+            .map(|o| o.gen_md(true).map(|md| md.trim().to_string()))
+            .collect::<Result<Vec<String>, Error>>()?
+            .join(operator);
+
+        match nested {
+            true => Ok(format!("({})", md)),
+            false => Ok(format!("{}\n", md)),
+        }
+    }
+}
+
+impl Display for LicenseExpression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (operands, operator) = match self {
+            LicenseExpression::License(license) => return write!(f, "{}", license),
+            LicenseExpression::And(operands) => (operands, " AND "),
+            LicenseExpression::Or(operands) => (operands, " OR "),
         };
 
+        let text = operands
+            .iter()
+            .map(|o| match o {
+                LicenseExpression::License(_) => o.to_string(),
+                _ => format!("({})", o),
+            })
+            .collect::<Vec<String>>()
+            .join(operator);
+
+        write!(f, "{}", text)
+    }
+}
+
+impl License {
+    /// Create a new License object from a name (string) base on the SupportedLicense methods
+    fn from_identifier(name: &str) -> Self {
         Self {
-            name: license,
+            name: SupportedLicense::from_str(name).unwrap_or(SupportedLicense::Unknown),
+            exception: None,
             path: None,
             url: None,
+            expression: None,
+            confidence: None,
+            matches: Vec::new(),
+        }
+    }
+
+    /// Create a new License object from a name or an SPDX expression (eg. `MIT OR Apache-2.0`), the deprecated
+    /// `MIT/Apache-2.0` form of the Cargo manifests is read as an `OR` expression
+    ///
+    /// If the name of the license is not recognized, the license will be set to `SupportedLicense::Unknown`
+    pub fn from_name(name: String) -> Self {
+        let name = trim(name).unwrap();
+
+        let expression = match name.contains(char::is_whitespace) || name.contains(':') {
+            true => name.clone(),
+            false => name.replace('/', " OR "),
+        };
+
+        match LicenseExpression::parse(&expression) {
+            Some(LicenseExpression::License(license)) => license,
+            Some(expression) => Self {
+                name: expression
+                    .licenses()
+                    .iter()
+                    .map(|license| license.name)
+                    .find(|name| *name != SupportedLicense::Unknown)
+                    .unwrap_or(SupportedLicense::Unknown),
+                exception: None,
+                path: None,
+                url: None,
+                expression: Some(Box::new(expression)),
+                confidence: None,
+                matches: Vec::new(),
+            },
+            None => License::from_identifier(&name),
+        }
+    }
+
+    /// Sets the location on the web of the license files, from their location in the project structure
+    pub fn set_urls(&mut self, url: impl Fn(&str) -> Option<String>) {
+        let licenses = match self.expression.as_mut() {
+            Some(expression) => expression.licenses_mut(),
+            None => vec![self],
+        };
+
+        for license in licenses {
+            if let Some(file_url) = license.path.as_deref().and_then(&url) {
+                license.url = Some(file_url);
+            }
+        }
+    }
+
+    /// Returns the license as text with the operands of its expression sorted, see `LicenseExpression::key`
+    pub fn key(&self) -> String {
+        match &self.expression {
+            Some(expression) => expression.key(),
+            None => self.to_string(),
+        }
+    }
+
+    /// Returns whether the license or one of the licenses of its expression was read from a file
+    pub fn has_file(&self) -> bool {
+        match &self.expression {
+            Some(expression) => expression.licenses().iter().any(|l| l.path.is_some()),
+            None => self.path.is_some(),
+        }
+    }
+
+    /// Returns the words introducing the licenses in the README, e.g. "Dual-licensed under"
+    pub fn licensing(&self) -> &'static str {
+        match self.expression.as_deref() {
+            Some(LicenseExpression::Or(operands)) if operands.len() == 2 => "Dual-licensed under",
+            Some(LicenseExpression::Or(_)) => "Multi-licensed under",
+            _ => "Published under",
         }
     }
 
     /// Create a new License object from a file, the new License object will contain the license name and the path to the file
     ///
-    /// If the license file is from a known platform (eg: github), the url will be set to the file location on the platform
+    /// The license is the one whose text is the most similar to the file, see [`License::rank`], the file mentioning a
+    /// license (eg. "MIT") is not enough. The license is unknown when the file holds less than [`MIN_COVERAGE`] of the
+    /// text of any license
     pub fn from_file(path: String) -> Self {
         let content = match fs::read_to_string(&path) {
             Ok(license) => license,
-            Err(_) => {
-                return Self {
-                    name: SupportedLicense::Unknown,
-                    path: None,
-                    url: None,
-                }
-            }
+            Err(_) => return License::from_identifier(""),
         };
 
-        match SupportedLicense::from_str(&content) {
-            Ok(license) => Self {
-                name: license,
-                path: Some(path),
-                url: None,
-            },
-            Err(_) => Self {
-                name: SupportedLicense::Unknown,
-                path: Some(path),
-                url: None,
-            },
+        let mut license = License::from_identifier("");
+        if let Some((name, coverage)) = License::rank(&content)
+            .first()
+            .filter(|(_, coverage)| *coverage >= MIN_COVERAGE)
+        {
+            let text = License::bigrams(&content);
+            let template = License::bigrams(&name.template().unwrap_or_default());
+
+            license.name = *name;
+            license.confidence = Some(coverage.min(License::coverage(&template, &text)));
+            license.matches = License::also_matching(*name, &text);
+        }
+        license.path = Some(path);
+
+        license
+    }
+
+    /// Returns whether the license was read from a file too different from the text of the license to be sure of it,
+    /// or matching other licenses as well
+    pub fn is_uncertain(&self) -> bool {
+        self.confidence
+            .is_some_and(|confidence| confidence < MIN_CONFIDENCE)
+            || !self.matches.is_empty()
+    }
+
+    /// Settles the license read from a file matching the text of other licenses as well (eg. `GPL-3.0-only` and
+    /// `GPL-3.0-or-later`) on the one declared elsewhere, like in the manifest of the project
+    pub fn prefer(&mut self, declared: &[SupportedLicense]) {
+        if self
+            .confidence
+            .is_some_and(|confidence| confidence < MIN_CONFIDENCE)
+            || self
+                .matches
+                .iter()
+                .any(|other| other.template() != self.name.template())
+        {
+            return;
+        }
+
+        if let Some(name) = declared
+            .iter()
+            .find(|name| **name == self.name || self.matches.contains(name))
+        {
+            self.name = *name;
+            self.matches.clear();
+        }
+    }
+
+    /// Returns the licenses the file of the license may be under, the most covered first, with the share of their text
+    /// found in it
+    pub fn alternatives(&self, count: usize) -> Vec<(License, f32)> {
+        let content = self
+            .path
+            .as_ref()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .unwrap_or_default();
+
+        License::rank(&content)
+            .into_iter()
+            .take(count)
+            .map(|(name, coverage)| {
+                let mut license = License::from_identifier("");
+                license.name = name;
+                license.path = self.path.clone();
+
+                (license, coverage)
+            })
+            .collect()
+    }
+
+    /// Returns the known licenses ranked by how much of their text is found in the given one, the most covered first
+    ///
+    /// The texts are compared by the pairs of consecutive words of the normalized texts, so that the formatting, the
+    /// copyright notice and a few changed words don't matter much
+    pub fn rank(text: &str) -> Vec<(SupportedLicense, f32)> {
+        let text = License::bigrams(text);
+
+        SupportedLicense::iter()
+            .filter_map(|license| {
+                let template = License::bigrams(License::terms(&license.template()?));
+
+                match template.is_empty() {
+                    true => None,
+                    false => Some((license, License::coverage(&text, &template))),
+                }
+            })
+            // the stable sort keeps the `-only` variants before the `-or-later` ones sharing their text
+            .sorted_by(|(_, a), (_, b)| b.total_cmp(a))
+            .collect()
+    }
+
+    /// Returns the terms of a license text without the appendix on how to apply them (eg. in the Apache and GNU
+    /// licenses), that the license files often leave out
+    fn terms(text: &str) -> &str {
+        match text.find("END OF TERMS AND CONDITIONS") {
+            Some(end) => &text[..end],
+            None => text,
+        }
+    }
+
+    /// Returns the share of the pairs of words of a text found in another one, from 0 to 1
+    fn coverage(
+        text: &HashMap<(String, String), usize>,
+        of: &HashMap<(String, String), usize>,
+    ) -> f32 {
+        let common: usize = of
+            .iter()
+            .map(|(bigram, count)| text.get(bigram).map_or(0, |c| *c.min(count)))
+            .sum();
+
+        match of.values().sum::<usize>() {
+            0 => 0.0,
+            total => common as f32 / total as f32,
         }
     }
 
+    /// Returns the licenses other than the given one the text matches as well: the ones sharing its text, like the
+    /// `-only` and `-or-later` variants, and the ones whose own words are found in the rest of the text, like the MIT
+    /// license in a file holding both the MIT and the Apache 2.0 texts
+    fn also_matching(
+        name: SupportedLicense,
+        text: &HashMap<(String, String), usize>,
+    ) -> Vec<SupportedLicense> {
+        let found = name.template().unwrap_or_default();
+        let found_bigrams = License::bigrams(&found);
+
+        // the words of the text not explained by the license found
+        let rest: HashMap<(String, String), usize> = text
+            .iter()
+            .map(|(bigram, count)| {
+                (
+                    bigram.clone(),
+                    count.saturating_sub(*found_bigrams.get(bigram).unwrap_or(&0)),
+                )
+            })
+            .filter(|(_, count)| *count > 0)
+            .collect();
+
+        SupportedLicense::iter()
+            .filter(|other| *other != name)
+            .filter(|other| match other.template() {
+                None => false,
+                Some(template) if template == found => true,
+                Some(template) => {
+                    // only the words of the other license that the license found doesn't have tell it apart
+                    let own: HashMap<(String, String), usize> =
+                        License::bigrams(License::terms(&template))
+                            .into_iter()
+                            .map(|(bigram, count)| {
+                                let count =
+                                    count.saturating_sub(*found_bigrams.get(&bigram).unwrap_or(&0));
+                                (bigram, count)
+                            })
+                            .filter(|(_, count)| *count > 0)
+                            .collect();
+
+                    own.values().sum::<usize>() >= MIN_OWN_BIGRAMS
+                        && License::coverage(&rest, &own) >= MIN_CONFIDENCE
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the pairs of consecutive words of a license text with the times they appear, the words are taken
+    /// lowercase without the punctuation and the template variables (eg. `{{year}}`)
+    fn bigrams(text: &str) -> HashMap<(String, String), usize> {
+        let variables = regex::Regex::new(r"\{\{[^}]*\}\}").unwrap();
+
+        let words: Vec<String> = variables
+            .replace_all(text, " ")
+            .to_lowercase()
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .map(|word| word.to_string())
+            .collect();
+
+        let mut bigrams: HashMap<(String, String), usize> = HashMap::new();
+        for pair in words.windows(2) {
+            *bigrams
+                .entry((pair[0].clone(), pair[1].clone()))
+                .or_default() += 1;
+        }
+
+        bigrams
+    }
+
+    /// Returns the name of the license file in the given path when it's named after its license, e.g.
+    /// `LICENSE-MIT` or `COPYING-GPL.txt`
+    fn suffix(path: &str) -> Option<String> {
+        let suffix_regex =
+            regex::Regex::new(r"(?i)(?:^|/)(?:license|copying)-([^/]+?)(?:\.(?:txt|md))?$")
+                .unwrap();
+
+        suffix_regex
+            .captures(path)
+            .map(|captures| captures[1].to_string())
+    }
+
     /// Scan the project for a license file and return a list of converter output
     /// each converter output will contain a License object with name and path set
     ///
@@ -181,7 +745,77 @@ impl License {
             }
         }
 
-        license_present.iter().for_each(|p| {
+        // the files named after their license in the same folder are the licenses the project may be used under,
+        // e.g. LICENSE-MIT and LICENSE-APACHE
+        let (named, others): (Vec<String>, Vec<String>) = license_present
+            .into_iter()
+            .partition(|p| License::suffix(p).is_some());
+
+        let dual = named
+            .into_iter()
+            .map(|p| {
+                let mut license = License::from_file(p.clone());
+
+                // the name of the file tells the license when its text doesn't, a file named after something else
+                // (eg. license-checker) isn't a license
+                if license.name == SupportedLicense::Unknown || license.is_uncertain() {
+                    match SupportedLicense::from_str(&License::suffix(&p).unwrap()) {
+                        Ok(name) => {
+                            license.name = name;
+                            license.confidence = None;
+                            license.matches.clear();
+                        }
+                        Err(_) => license.name = SupportedLicense::Unknown,
+                    }
+                }
+                license
+            })
+            .filter(|license| license.name != SupportedLicense::Unknown)
+            .into_group_map_by(|license| {
+                license
+                    .path
+                    .as_ref()
+                    .and_then(|p| p.rsplit_once('/').map(|(folder, _)| folder.to_string()))
+                    .unwrap_or_default()
+            });
+
+        for (folder, licenses) in dual.into_iter().sorted_by(|a, b| a.0.cmp(&b.0)) {
+            let mut converter = ConverterOutput::empty();
+
+            converter.license = match licenses.len() {
+                1 => licenses.into_iter().next(),
+                _ => {
+                    converter.source_config_file_path = format!("{}/LICENSE-*", folder);
+
+                    Some(License {
+                        name: licenses[0].name,
+                        exception: None,
+                        path: None,
+                        url: None,
+                        expression: Some(Box::new(LicenseExpression::Or(
+                            licenses
+                                .into_iter()
+                                .map(LicenseExpression::License)
+                                .collect(),
+                        ))),
+                        confidence: None,
+                        matches: Vec::new(),
+                    })
+                }
+            };
+
+            if converter.source_config_file_path.is_empty() {
+                converter.source_config_file_path = converter
+                    .license
+                    .as_ref()
+                    .and_then(|l| l.path.clone())
+                    .unwrap_or_default();
+            }
+
+            converter_outputs.push(converter);
+        }
+
+        others.iter().for_each(|p| {
             let mut converter = ConverterOutput::empty();
             converter.source_config_file_path = p.to_string();
             converter.license = Option::from(License::from_file(p.to_string()));
@@ -192,64 +826,84 @@ impl License {
         Ok(converter_outputs)
     }
 
-    /// Create a license file in the project
+    /// Create the license files missing from the project, a project with more than one license gets a file for
+    /// each of them named after the license (eg. `LICENSE-MIT`), the paths of the files are set in the licenses
+    ///
+    /// The copyright notices are given to the holder, the main author of the project for example
     pub fn create(
         project_location: &str,
-        license: &License,
-        project_name: Option<String>,
-    ) -> Result<Option<String>, Error> {
-        // if there is a path for the license or the license is unknown, dont create a license
-        if license.path.is_some() || license.name == SupportedLicense::Unknown {
-            return Ok(None);
-        }
-
-        let output_path = format!("{}/LICENSE", project_location);
-        let mut license_file = match File::create(output_path.clone()) {
-            Ok(f) => f,
-            Err(e) => {
-                return Err(Error::new(e));
-            }
-        };
-
-        // load the right license template based on the license name
-        let license_contents = match license.name {
-            SupportedLicense::Apache20 => read_util_file_contents(UtilityPath::Apache20),
-            SupportedLicense::MIT => read_util_file_contents(UtilityPath::MIT),
-            SupportedLicense::GNUGeneralPublicLicense => {
-                read_util_file_contents(UtilityPath::GNUGPL)
-            }
-            SupportedLicense::CreativeCommonsAttributionShareAlike40 => {
-                read_util_file_contents(UtilityPath::CreativeCommonsAttributionShareAlike40)
-            }
-            SupportedLicense::Unknown => {
-                return Ok(None);
-            }
-        };
-
+        license: &mut License,
+        holder: Option<String>,
+    ) -> Result<(), Error> {
         let year = chrono::Utc::now().year().to_string();
 
         // some license file require project info
         let license_data = json!({
             "year": year,
-            "project_name": project_name.unwrap_or("".to_string())
+            "holder": holder.unwrap_or("".to_string())
         });
 
-        let mut handlebars = Handlebars::new();
+        let (licenses, single) = match license.expression.as_mut() {
+            Some(expression) => (expression.licenses_mut(), false),
+            None => (vec![license], true),
+        };
+
+        for license in licenses {
+            // if there is a path for the license or the license is unknown, dont create a license
+            if license.path.is_some() || license.name == SupportedLicense::Unknown {
+                continue;
+            }
+
+            // load the right license template based on the license name
+            let license_contents = match license.name.template() {
+                Some(license_contents) => license_contents,
+                None => continue,
+            };
+
+            let output_path = match single {
+                true => format!("{}/LICENSE", project_location),
+                false => format!(
+                    "{}/LICENSE-{}",
+                    project_location,
+                    license.name.file_suffix()
+                ),
+            };
+
+            // the same license may appear twice in an expression, e.g. `MIT OR (MIT AND Apache-2.0)`
+            if !Path::new(&output_path).exists() {
+                let mut license_file = match File::create(output_path.clone()) {
+                    Ok(f) => f,
+                    Err(e) => {
+                        return Err(Error::new(e));
+                    }
+                };
+
+                // the license files are plain text, the names must not be escaped
+                let mut handlebars = Handlebars::new();
+                handlebars.register_escape_fn(handlebars::no_escape);
 
-        handlebars
-            .register_template_string("license_tpl", license_contents)
-            .unwrap();
+                handlebars
+                    .register_template_string("license_tpl", license_contents)
+                    .unwrap();
 
-        let render = handlebars.render("license_tpl", &license_data).unwrap();
+                let render = handlebars.render("license_tpl", &license_data).unwrap();
+
+                license_file.write_all(render.as_bytes())?;
+            }
 
-        license_file.write_all(render.as_bytes())?;
+            license.path = Some(output_path);
+        }
 
-        Ok(Option::from(output_path))
+        Ok(())
     }
 }
 
 impl GenMarkdown for License {
     fn gen_md(&self) -> Result<String, anyhow::Error> {
+        if let Some(expression) = &self.expression {
+            return expression.gen_md(false);
+        }
+
         let license_tpl = paths::read_util_file_contents(paths::UtilityPath::LicenseReadme);
         let mut handlebars = handlebars::Handlebars::new();
         handlebars.register_template_string("license_tpl", license_tpl)?;
@@ -257,11 +911,13 @@ impl GenMarkdown for License {
         let data = if let Some(url) = &self.url {
             json!({
                 "name": self.name.to_string(),
+                "exception": self.exception,
                 "target": url.split(".git").collect::<Vec<&str>>()[0]
             })
         } else {
             json!({
-                "name": self.name.to_string()
+                "name": self.name.to_string(),
+                "exception": self.exception,
             })
         };
 
@@ -275,6 +931,127 @@ impl Display for License {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         // we don't want to print the path or the url
         // to avoid duplicate information during merge process
-        write!(f, "{}", self.name.to_string())
+        match (&self.expression, &self.exception) {
+            (Some(expression), _) => write!(f, "{}", expression),
+            (None, Some(exception)) => write!(f, "{} WITH {}", self.name.to_string(), exception),
+            (None, None) => write!(f, "{}", self.name.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(expression: &LicenseExpression) -> Vec<SupportedLicense> {
+        expression.licenses().iter().map(|l| l.name).collect()
+    }
+
+    #[test]
+    fn parses_a_single_license() {
+        let parsed = LicenseExpression::parse("MIT").unwrap();
+
+        assert!(
+            matches!(parsed, LicenseExpression::License(ref l) if l.name == SupportedLicense::MIT)
+        );
+    }
+
+    #[test]
+    fn and_takes_precedence_over_or() {
+        let parsed = LicenseExpression::parse("MIT OR Apache-2.0 AND Zlib").unwrap();
+
+        match &parsed {
+            LicenseExpression::Or(operands) => {
+                assert_eq!(operands.len(), 2);
+                assert!(matches!(operands[1], LicenseExpression::And(_)));
+            }
+            _ => panic!("expected an OR expression, got {}", parsed),
+        }
+        assert_eq!(
+            names(&parsed),
+            vec![
+                SupportedLicense::MIT,
+                SupportedLicense::Apache20,
+                SupportedLicense::Zlib
+            ]
+        );
+    }
+
+    #[test]
+    fn parentheses_override_the_precedence() {
+        let parsed = LicenseExpression::parse("(MIT OR Apache-2.0) AND Zlib").unwrap();
+
+        match &parsed {
+            LicenseExpression::And(operands) => {
+                assert!(matches!(operands[0], LicenseExpression::Or(_)));
+            }
+            _ => panic!("expected an AND expression, got {}", parsed),
+        }
+        assert_eq!(parsed.to_string(), "(MIT OR Apache-2.0) AND Zlib");
+    }
+
+    #[test]
+    fn parses_the_exceptions() {
+        let parsed =
+            LicenseExpression::parse("GPL-2.0-only WITH Classpath-exception-2.0 OR MIT").unwrap();
+
+        let licenses = parsed.licenses();
+        assert_eq!(licenses[0].name, SupportedLicense::GPL20Only);
+        assert_eq!(
+            licenses[0].exception.as_deref(),
+            Some("Classpath-exception-2.0")
+        );
+        assert_eq!(licenses[1].exception, None);
+    }
+
+    #[test]
+    fn reads_the_lowercase_operators_as_names() {
+        let parsed = LicenseExpression::parse("MIT or Apache-2.0").unwrap();
+
+        assert!(matches!(parsed, LicenseExpression::License(_)));
+    }
+
+    #[test]
+    fn rejects_malformed_expressions() {
+        for expression in [
+            "",
+            "MIT OR",
+            "AND MIT",
+            "(MIT OR Apache-2.0",
+            "MIT OR Apache-2.0)",
+            "MIT WITH",
+            "MIT () Zlib",
+        ] {
+            assert!(
+                LicenseExpression::parse(expression).is_none(),
+                "{:?} should not parse",
+                expression
+            );
+        }
+    }
+
+    #[test]
+    fn keys_ignore_the_order_of_the_operands() {
+        let a = License::from_name("MIT OR Apache-2.0".to_string());
+        let b = License::from_name("Apache-2.0 OR MIT".to_string());
+
+        assert_eq!(a.key(), b.key());
+    }
+
+    #[test]
+    fn reads_the_license_of_a_file() {
+        let mut license = License::from_file("LICENSE".to_string());
+        license.prefer(&[SupportedLicense::GPL30Only]);
+
+        assert_eq!(license.name, SupportedLicense::GPL30Only);
+        assert!(!license.is_uncertain());
+    }
+
+    #[test]
+    fn leaves_out_the_files_barely_holding_a_license() {
+        let license = License::from_file("conf/tpl/readme/LICENSE.md".to_string());
+
+        assert_eq!(license.name, SupportedLicense::Unknown);
+        assert!(!license.is_uncertain());
     }
 }
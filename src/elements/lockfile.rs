@@ -0,0 +1,317 @@
+use std::{fs, path::Path};
+
+use anyhow::{anyhow, Error};
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::converter::{ConverterOutput, Dependency};
+
+/// A package pinned to an exact version by a lockfile, it may be a transitive dependency
+#[derive(Debug, Clone, Serialize)]
+pub struct LockedPackage {
+    pub name: String,
+    pub version: String,
+    /// The ecosystem of the lockfile, a package of the same name may exist in more than one (eg. `log`)
+    pub ecosystem: Ecosystem,
+}
+
+/// The package ecosystems of the supported lockfiles
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+pub enum Ecosystem {
+    Cargo,
+    Npm,
+    Composer,
+}
+
+impl Ecosystem {
+    /// Returns the ecosystem of the dependencies declared by the given manifest, e.g. `Cargo.toml`
+    pub fn from_manifest(path: &str) -> Option<Ecosystem> {
+        match Path::new(path).file_name().and_then(|f| f.to_str())? {
+            "Cargo.toml" => Some(Ecosystem::Cargo),
+            "package.json" => Some(Ecosystem::Npm),
+            "composer.json" => Some(Ecosystem::Composer),
+            _ => None,
+        }
+    }
+}
+
+/// The supported lockfiles
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lockfile {
+    CargoLock,
+    PackageLockJson,
+    YarnLock,
+    PnpmLockYaml,
+    ComposerLock,
+}
+
+impl Lockfile {
+    fn from_file_name(file_name: &str) -> Option<Lockfile> {
+        match file_name {
+            "Cargo.lock" => Some(Lockfile::CargoLock),
+            "package-lock.json" | "npm-shrinkwrap.json" => Some(Lockfile::PackageLockJson),
+            "yarn.lock" => Some(Lockfile::YarnLock),
+            "pnpm-lock.yaml" => Some(Lockfile::PnpmLockYaml),
+            "composer.lock" => Some(Lockfile::ComposerLock),
+            _ => None,
+        }
+    }
+
+    fn ecosystem(&self) -> Ecosystem {
+        match self {
+            Lockfile::CargoLock => Ecosystem::Cargo,
+            Lockfile::PackageLockJson | Lockfile::YarnLock | Lockfile::PnpmLockYaml => {
+                Ecosystem::Npm
+            }
+            Lockfile::ComposerLock => Ecosystem::Composer,
+        }
+    }
+
+    /// Scan the project for lockfiles and return a list of converter output
+    /// each converter output will contain the packages locked by one lockfile
+    pub fn scan(paths: &Vec<String>) -> Result<Vec<ConverterOutput>, Error> {
+        let mut converter_outputs: Vec<ConverterOutput> = vec![];
+
+        for path in paths {
+            let lockfile = match Path::new(path)
+                .file_name()
+                .and_then(|f| f.to_str())
+                .and_then(Lockfile::from_file_name)
+            {
+                Some(lockfile) => lockfile,
+                None => continue,
+            };
+
+            // an unreadable lockfile is not a reason to stop, the declared versions are still there
+            let packages = match fs::read_to_string(path)
+                .map_err(Error::new)
+                .and_then(|c| lockfile.parse(&c))
+            {
+                Ok(packages) => packages,
+                Err(_) => continue,
+            };
+
+            let mut converter = ConverterOutput::empty();
+            converter.source_config_file_path = path.to_string();
+            converter.locked_packages = Some(packages);
+
+            converter_outputs.push(converter);
+        }
+
+        Ok(converter_outputs)
+    }
+
+    /// Returns the packages locked in the given lockfile contents
+    fn parse(&self, contents: &str) -> Result<Vec<LockedPackage>, Error> {
+        match self {
+            Lockfile::CargoLock => {
+                let lock: Value = toml::from_str(contents)?;
+
+                Ok(lock["package"]
+                    .as_array()
+                    .map(|packages| {
+                        packages
+                            .iter()
+                            .filter_map(|p| self.locked(&p["name"], &p["version"]))
+                            .collect()
+                    })
+                    .unwrap_or_default())
+            }
+            Lockfile::ComposerLock => {
+                let lock: Value = serde_json::from_str(contents)?;
+
+                Ok(["packages", "packages-dev"]
+                    .iter()
+                    .filter_map(|key| lock[key].as_array())
+                    .flatten()
+                    .filter_map(|p| self.locked(&p["name"], &p["version"]))
+                    .collect())
+            }
+            Lockfile::PackageLockJson => {
+                let lock: Value = serde_json::from_str(contents)?;
+
+                // lockfileVersion 2 and 3 list every package by its node_modules path
+                if let Some(packages) = lock["packages"].as_object() {
+                    return Ok(packages
+                        .iter()
+                        .filter_map(|(path, p)| {
+                            let name = path.rsplit("node_modules/").next()?;
+                            self.locked(&Value::from(name), &p["version"])
+                        })
+                        .filter(|p| !p.name.is_empty())
+                        .collect());
+                }
+
+                // lockfileVersion 1 nests the dependencies of the dependencies
+                let mut packages = vec![];
+                Lockfile::parse_npm_v1(&lock["dependencies"], &mut packages);
+                Ok(packages)
+            }
+            Lockfile::YarnLock => Ok(Lockfile::parse_yarn(contents)),
+            Lockfile::PnpmLockYaml => {
+                let lock: Value = serde_yaml::from_str(contents)?;
+
+                let packages = match lock["packages"].as_object() {
+                    Some(packages) => packages,
+                    None => return Err(anyhow!("No packages found in pnpm-lock.yaml")),
+                };
+
+                // the version is a number in the old lockfiles (5.4) and a string since v6 ('6.0')
+                let lockfile_version = lock["lockfileVersion"]
+                    .as_f64()
+                    .or_else(|| {
+                        lock["lockfileVersion"]
+                            .as_str()
+                            .and_then(|v| v.parse().ok())
+                    })
+                    .unwrap_or(6.0);
+
+                Ok(packages
+                    .keys()
+                    .filter_map(|key| Lockfile::parse_pnpm_key(key, lockfile_version < 6.0))
+                    .collect())
+            }
+        }
+    }
+
+    fn locked(&self, name: &Value, version: &Value) -> Option<LockedPackage> {
+        Some(LockedPackage {
+            name: name.as_str()?.to_string(),
+            version: version.as_str()?.to_string(),
+            ecosystem: self.ecosystem(),
+        })
+    }
+
+    fn parse_npm_v1(dependencies: &Value, packages: &mut Vec<LockedPackage>) {
+        let dependencies = match dependencies.as_object() {
+            Some(dependencies) => dependencies,
+            None => return,
+        };
+
+        for (name, dependency) in dependencies {
+            if let Some(package) = Lockfile::PackageLockJson
+                .locked(&Value::from(name.as_str()), &dependency["version"])
+            {
+                packages.push(package);
+            }
+
+            Lockfile::parse_npm_v1(&dependency["dependencies"], packages);
+        }
+    }
+
+    /// Parses both the classic (v1) and the berry yarn.lock, an entry looks like:
+    ///
+    /// ```text
+    /// "@babel/core@^7.0.0", "@babel/core@^7.1.0":
+    ///   version "7.1.2"
+    /// ```
+    fn parse_yarn(contents: &str) -> Vec<LockedPackage> {
+        let mut packages: Vec<LockedPackage> = vec![];
+        let mut current: Option<String> = None;
+
+        for line in contents.lines() {
+            if line.starts_with('#') || line.trim().is_empty() {
+                continue;
+            }
+
+            if !line.starts_with(char::is_whitespace) {
+                // the name is the first descriptor without the range, scoped packages start with @
+                current = line
+                    .trim_end_matches(':')
+                    .split(',')
+                    .next()
+                    .map(|d| d.trim().trim_matches('"'))
+                    .and_then(|d| d.get(1..)?.find('@').map(|i| d[..i + 1].to_string()));
+                continue;
+            }
+
+            let trimmed = line.trim();
+            let version = trimmed
+                .strip_prefix("version ")
+                .or_else(|| trimmed.strip_prefix("version: "));
+
+            // the version is not always the first field of the entry
+            if let (Some(version), Some(name)) = (version, current.as_ref()) {
+                packages.push(LockedPackage {
+                    name: name.to_string(),
+                    version: version.trim().trim_matches('"').to_string(),
+                    ecosystem: Ecosystem::Npm,
+                });
+                current = None;
+            }
+        }
+
+        packages
+    }
+
+    /// Parses a key of the pnpm-lock.yaml packages, the format changed across lockfile versions:
+    /// `/name/1.0.0_peer@1.0.0` (v5), `/name@1.0.0(peer@1.0.0)` (v6) and `name@1.0.0` (v9)
+    fn parse_pnpm_key(key: &str, slash_separated: bool) -> Option<LockedPackage> {
+        let key = key.trim_start_matches('/');
+
+        let (name, version) = if slash_separated {
+            let (name, version) = key.rsplit_once('/')?;
+            (name, version.split('_').next().unwrap_or(version))
+        } else {
+            let key = key.split('(').next().unwrap_or(key);
+            let i = key.rfind('@').filter(|i| *i > 0)?;
+            (&key[..i], &key[i + 1..])
+        };
+
+        Some(LockedPackage {
+            name: name.to_string(),
+            version: version.to_string(),
+            ecosystem: Ecosystem::Npm,
+        })
+    }
+}
+
+impl LockedPackage {
+    /// Finds the version locked for the given dependency of the given ecosystem, when a package is locked at
+    /// multiple versions the one compatible with the requirement is preferred
+    pub fn resolve(
+        dependency: &Dependency,
+        ecosystem: Ecosystem,
+        locked: &[LockedPackage],
+    ) -> Option<String> {
+        let candidates: Vec<&LockedPackage> = locked
+            .iter()
+            .filter(|p| p.ecosystem == ecosystem && p.name == dependency.name)
+            .collect();
+
+        let requirement = dependency.version.as_ref().map(|v| {
+            v.trim_start_matches(|c: char| !c.is_ascii_digit())
+                .split(|c: char| !c.is_ascii_digit() && c != '.')
+                .next()
+                .unwrap_or("")
+                .to_string()
+        });
+
+        let compatible = candidates.iter().find(|p| match &requirement {
+            Some(requirement) if !requirement.is_empty() => {
+                LockedPackage::same_compatibility_range(requirement, &p.version)
+            }
+            _ => false,
+        });
+
+        compatible.or(candidates.first()).map(|p| p.version.clone())
+    }
+
+    /// Two versions are in the same range when they share every component up to the first non-zero one,
+    /// e.g. `1.2` and `1.9.0` or `0.4.1` and `0.4.26`
+    fn same_compatibility_range(requirement: &str, version: &str) -> bool {
+        let version = version.trim_start_matches('v');
+
+        for (r, v) in requirement.split('.').zip(version.split('.')) {
+            if r != v {
+                return false;
+            }
+
+            if r != "0" {
+                return true;
+            }
+        }
+
+        true
+    }
+}
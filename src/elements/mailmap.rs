@@ -0,0 +1,204 @@
+use std::{fs, path::PathBuf};
+
+use crate::{
+    converter::{Contributor, Contributors},
+    elements::repository::Repository,
+};
+
+/// An entry of the .mailmap file, it replaces the name and/or the email of the matching identities
+#[derive(Debug, Clone)]
+struct MailmapEntry {
+    proper_name: Option<String>,
+    proper_email: Option<String>,
+    /// The name the entry applies to, any name with the commit email matches when missing
+    commit_name: Option<String>,
+    commit_email: String,
+}
+
+/// The canonical identities of the contributors as declared in the .mailmap file of the project
+///
+/// Reference: https://git-scm.com/docs/gitmailmap
+#[derive(Debug, Clone, Default)]
+pub struct Mailmap {
+    entries: Vec<MailmapEntry>,
+}
+
+impl Mailmap {
+    /// Loads the .mailmap file at the root of the repository of the project, or at the project root when the project
+    /// is not in a repository, an empty mailmap is returned when there is none
+    pub fn load(project_location: &str) -> Mailmap {
+        let root = Repository::discover(project_location)
+            .ok()
+            .and_then(|repo| repo.workdir().map(|workdir| workdir.to_path_buf()))
+            .unwrap_or(PathBuf::from(project_location));
+
+        match fs::read_to_string(root.join(".mailmap")) {
+            Ok(contents) => Mailmap::parse(&contents),
+            Err(_) => Mailmap::default(),
+        }
+    }
+
+    /// Parses the lines of a .mailmap file, each one in one of the forms
+    ///
+    /// ```text
+    /// Proper Name <commit@email.xx>
+    /// <proper@email.xx> <commit@email.xx>
+    /// Proper Name <proper@email.xx> <commit@email.xx>
+    /// Proper Name <proper@email.xx> Commit Name <commit@email.xx>
+    /// ```
+    fn parse(contents: &str) -> Mailmap {
+        let line_regex = regex::Regex::new(r"^([^<#]*)<([^>]*)>\s*(?:([^<#]*)<([^>]*)>)?").unwrap();
+
+        let non_empty = |value: Option<regex::Match>| {
+            value
+                .map(|v| v.as_str().trim().to_string())
+                .filter(|v| !v.is_empty())
+        };
+
+        let entries = contents
+            .lines()
+            .filter_map(|line| {
+                let captures = line_regex.captures(line.trim())?;
+
+                let entry = match non_empty(captures.get(4)) {
+                    Some(commit_email) => MailmapEntry {
+                        proper_name: non_empty(captures.get(1)),
+                        proper_email: non_empty(captures.get(2)),
+                        commit_name: non_empty(captures.get(3)),
+                        commit_email,
+                    },
+                    None => MailmapEntry {
+                        proper_name: non_empty(captures.get(1)),
+                        proper_email: None,
+                        commit_name: None,
+                        commit_email: non_empty(captures.get(2))?,
+                    },
+                };
+
+                Some(entry)
+            })
+            .collect();
+
+        Mailmap { entries }
+    }
+
+    /// Returns the canonical identity of the given contributor, the contributor itself when no entry matches
+    ///
+    /// The entries matching both the name and the email win over the ones matching the email only
+    pub fn resolve(&self, contributor: &Contributor) -> Contributor {
+        let email = match &contributor.email {
+            Some(email) => email.to_lowercase(),
+            None => return contributor.clone(),
+        };
+        let name = contributor.name.as_ref().map(|n| n.to_lowercase());
+
+        let matching = self
+            .entries
+            .iter()
+            .filter(|entry| entry.commit_email.to_lowercase() == email)
+            .filter(|entry| match &entry.commit_name {
+                Some(commit_name) => Some(commit_name.to_lowercase()) == name,
+                None => true,
+            })
+            .max_by_key(|entry| entry.commit_name.is_some());
+
+        match matching {
+            Some(entry) => Contributor {
+                name: entry.proper_name.clone().or(contributor.name.clone()),
+                email: entry.proper_email.clone().or(contributor.email.clone()),
+                url: contributor.url.clone(),
+                stats: contributor.stats.clone(),
+            },
+            None => contributor.clone(),
+        }
+    }
+
+    /// Returns the canonical identities of the given contributors, keeping their order
+    pub fn resolve_all(&self, contributors: &Contributors) -> Contributors {
+        contributors
+            .iter()
+            .map(|contributor| self.resolve(contributor))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn contributor(name: &str, email: &str) -> Contributor {
+        Contributor {
+            name: Some(name.to_string()),
+            email: Some(email.to_string()),
+            url: None,
+            stats: None,
+        }
+    }
+
+    #[test]
+    fn parses_proper_name() {
+        let mailmap = Mailmap::parse("Jane Doe <jane@example.com>");
+        let entry = &mailmap.entries[0];
+
+        assert_eq!(entry.proper_name.as_deref(), Some("Jane Doe"));
+        assert_eq!(entry.proper_email, None);
+        assert_eq!(entry.commit_name, None);
+        assert_eq!(entry.commit_email, "jane@example.com");
+    }
+
+    #[test]
+    fn parses_proper_email() {
+        let mailmap = Mailmap::parse("<jane@example.com> <jd@old.example.com>");
+        let entry = &mailmap.entries[0];
+
+        assert_eq!(entry.proper_name, None);
+        assert_eq!(entry.proper_email.as_deref(), Some("jane@example.com"));
+        assert_eq!(entry.commit_name, None);
+        assert_eq!(entry.commit_email, "jd@old.example.com");
+    }
+
+    #[test]
+    fn parses_proper_name_and_email() {
+        let mailmap = Mailmap::parse("Jane Doe <jane@example.com> <jd@old.example.com>");
+        let entry = &mailmap.entries[0];
+
+        assert_eq!(entry.proper_name.as_deref(), Some("Jane Doe"));
+        assert_eq!(entry.proper_email.as_deref(), Some("jane@example.com"));
+        assert_eq!(entry.commit_name, None);
+        assert_eq!(entry.commit_email, "jd@old.example.com");
+    }
+
+    #[test]
+    fn parses_commit_name_and_email() {
+        let mailmap =
+            Mailmap::parse("Jane Doe <jane@example.com> jd <jd@old.example.com> # the old laptop");
+        let entry = &mailmap.entries[0];
+
+        assert_eq!(entry.proper_name.as_deref(), Some("Jane Doe"));
+        assert_eq!(entry.proper_email.as_deref(), Some("jane@example.com"));
+        assert_eq!(entry.commit_name.as_deref(), Some("jd"));
+        assert_eq!(entry.commit_email, "jd@old.example.com");
+    }
+
+    #[test]
+    fn skips_comments_and_blank_lines() {
+        let mailmap = Mailmap::parse("# the team\n\nJane Doe <jane@example.com>\n");
+
+        assert_eq!(mailmap.entries.len(), 1);
+    }
+
+    #[test]
+    fn prefers_the_entries_matching_the_name() {
+        let mailmap = Mailmap::parse(
+            "Jane Doe <jane@example.com> <shared@example.com>\nJohn Roe <john@example.com> john <shared@example.com>",
+        );
+
+        let john = mailmap.resolve(&contributor("John", "Shared@example.com"));
+        let jane = mailmap.resolve(&contributor("someone", "shared@example.com"));
+
+        assert_eq!(john.name.as_deref(), Some("John Roe"));
+        assert_eq!(john.email.as_deref(), Some("john@example.com"));
+        assert_eq!(jane.name.as_deref(), Some("Jane Doe"));
+        assert_eq!(jane.email.as_deref(), Some("jane@example.com"));
+    }
+}
@@ -1,3 +1,14 @@
+pub mod cargo;
+pub mod changelog;
+pub mod ci;
+pub mod docker;
+pub mod env;
 pub mod funding;
+pub mod language;
 pub mod license;
+pub mod lockfile;
+pub mod mailmap;
 pub mod repository;
+pub mod script;
+pub mod structure;
+pub mod toolchain;
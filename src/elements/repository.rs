@@ -1,15 +1,17 @@
-use std::{collections::HashMap, str::FromStr};
+use std::{cmp::Ordering, collections::HashMap, fmt::Display, str::FromStr};
 
 use anyhow::Error;
-use git2::Repository as GitRepository;
+use git2::{Repository as GitRepository, StatusOptions};
 use itertools::Itertools;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use strum::EnumIter;
 
 use crate::{
-    converter::{Contributor, Contributors, ConverterOutput},
+    config::ContributorsConfig,
+    converter::{Contributor, ContributorStats, Contributors, ConverterOutput},
     dialoguer,
+    elements::mailmap::Mailmap,
     utils::{paths, trim, GenMarkdown},
 };
 
@@ -25,83 +27,429 @@ pub struct Repository {
     ///
     /// e.g. https://github.com/writeme-project/writeme.git -> **writeme-project/writeme**
     pub sign: Option<String>,
+    /// The host serving the repository, with the port if any
+    ///
+    /// e.g. git@gitlab.example.org:group/repo.git -> **gitlab.example.org**
+    pub host: Option<String>,
     pub platform: RepositoryPlatform,
 }
 
+/// The latest semver tag reachable from the head of the repository, e.g. `v1.2.3` or `1.2.3`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct VersionTag {
+    pub tag: String,
+    /// The version in the tag, without the `v` prefix
+    pub version: String,
+    /// The number of commits made after the tag
+    pub commits_since: usize,
+    /// Whether the tracked files have uncommitted changes
+    pub dirty: bool,
+}
+
+impl VersionTag {
+    /// Returns the latest semver tag the head of the repository descends from, if any
+    pub fn scan(repo: &GitRepository) -> Option<VersionTag> {
+        let semver = regex::Regex::new(
+            r"^v?(\d+)\.(\d+)\.(\d+)(?:-([0-9A-Za-z.-]+))?(?:\+[0-9A-Za-z.-]+)?$",
+        )
+        .unwrap();
+
+        let head = repo.head().ok()?.peel_to_commit().ok()?.id();
+
+        let (tag, target) = repo
+            .tag_names(None)
+            .ok()?
+            .iter()
+            .flatten()
+            .filter(|name| semver.is_match(name))
+            .filter_map(|name| {
+                let target = repo
+                    .revparse_single(&format!("refs/tags/{}", name))
+                    .and_then(|object| object.peel_to_commit())
+                    .ok()?
+                    .id();
+
+                let reachable =
+                    target == head || repo.graph_descendant_of(head, target).unwrap_or(false);
+
+                reachable.then(|| (name.to_string(), target))
+            })
+            .max_by(|(a, _), (b, _)| VersionTag::compare(&semver, a, b))?;
+
+        let commits_since = repo
+            .graph_ahead_behind(head, target)
+            .map(|(ahead, _)| ahead)
+            .unwrap_or(0);
+
+        // untracked files are not part of any version, as for `git describe --dirty`
+        let dirty = repo
+            .statuses(Some(StatusOptions::new().include_untracked(false)))
+            .map(|statuses| !statuses.is_empty())
+            .unwrap_or(false);
+
+        Some(VersionTag {
+            version: tag.trim_start_matches('v').to_string(),
+            tag,
+            commits_since,
+            dirty,
+        })
+    }
+
+    /// Compares two semver tags, a pre-release comes before the release of the same version
+    fn compare(semver: &regex::Regex, a: &str, b: &str) -> Ordering {
+        let key = |tag: &str| {
+            let captures = semver.captures(tag).unwrap();
+            let number = |i: usize| captures[i].parse::<u64>().unwrap_or(0);
+            let pre_release = captures.get(4).map(|p| p.as_str().to_string());
+
+            (
+                number(1),
+                number(2),
+                number(3),
+                pre_release.is_none(),
+                pre_release,
+            )
+        };
+
+        key(a).cmp(&key(b))
+    }
+}
+
+/// A repository nested in the repository of the project, declared in its .gitmodules
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Submodule {
+    pub name: String,
+    /// The path of the submodule relative to the root of the repository
+    pub path: String,
+    pub url: Option<String>,
+}
+
+impl Submodule {
+    /// Returns the submodules of the repository, in the order they are declared, the relative urls (eg. `../lib.git`)
+    /// are resolved against the url of the remote of the repository
+    pub fn scan(repo: &GitRepository, remote_url: Option<&str>) -> Vec<Submodule> {
+        repo.submodules()
+            .unwrap_or_default()
+            .iter()
+            .map(|submodule| Submodule {
+                name: submodule.name().unwrap_or_default().to_string(),
+                path: submodule.path().to_string_lossy().to_string(),
+                url: submodule.url().map(|url| {
+                    match (url.starts_with("./") || url.starts_with("../"), remote_url) {
+                        (true, Some(remote_url)) => Submodule::resolve_url(remote_url, url),
+                        _ => url.to_string(),
+                    }
+                }),
+            })
+            .collect()
+    }
+
+    /// Resolves a relative submodule url against the url of the remote, each `../` drops the last part of the remote
+    /// url, e.g. `git@github.com:acme/app.git` and `../lib.git` give `git@github.com:acme/lib.git`
+    fn resolve_url(remote_url: &str, relative: &str) -> String {
+        let remote_url = remote_url.trim_end_matches('/');
+
+        // the path of a web url starts after the host, the one of an scp-like url after the colon
+        let path_start = match remote_url.find("://") {
+            Some(i) => remote_url[i + 3..]
+                .find('/')
+                .map_or(remote_url.len(), |j| i + 3 + j),
+            None => remote_url.find(':').map_or(0, |i| i + 1),
+        };
+        let (address, path) = remote_url.split_at(path_start);
+
+        let mut segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+        for part in relative.split('/') {
+            match part {
+                "" | "." => {}
+                ".." => {
+                    segments.pop();
+                }
+                part => segments.push(part),
+            }
+        }
+
+        let separator = match path.starts_with('/') {
+            true => "/",
+            false => "",
+        };
+
+        format!("{}{}{}", address, separator, segments.join("/"))
+    }
+}
+
+/// Returns the patterns of the files stored with Git LFS, as listed in the .gitattributes files tracked by the
+/// repository (eg. `*.psd filter=lfs diff=lfs merge=lfs -text`), the patterns of a nested file are prefixed with its
+/// folder
+pub fn lfs_patterns(repo: &GitRepository) -> Vec<String> {
+    let workdir = match repo.workdir() {
+        Some(workdir) => workdir,
+        None => return vec![],
+    };
+
+    let mut folders: Vec<String> = repo
+        .index()
+        .map(|index| {
+            index
+                .iter()
+                .map(|entry| String::from_utf8_lossy(&entry.path).to_string())
+                .filter_map(|path| match path.as_str() {
+                    ".gitattributes" => Some(String::new()),
+                    _ => path
+                        .strip_suffix("/.gitattributes")
+                        .map(|folder| format!("{}/", folder)),
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    // a .gitattributes not committed yet at the root still counts
+    if !folders.contains(&String::new()) {
+        folders.insert(0, String::new());
+    }
+    folders.sort();
+
+    folders
+        .iter()
+        .flat_map(|folder| {
+            std::fs::read_to_string(workdir.join(folder).join(".gitattributes"))
+                .unwrap_or_default()
+                .lines()
+                .filter(|line| !line.trim_start().starts_with('#'))
+                .filter_map(|line| {
+                    let mut fields = line.split_whitespace();
+                    let pattern = fields.next()?;
+
+                    fields
+                        .any(|attribute| attribute == "filter=lfs")
+                        .then(|| format!("{}{}", folder, pattern.trim_start_matches('/')))
+                })
+                .collect::<Vec<String>>()
+        })
+        .collect()
+}
+
+impl Display for VersionTag {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "tag {}", self.tag)?;
+
+        if self.commits_since > 0 {
+            let plural = if self.commits_since == 1 { "" } else { "s" };
+            write!(f, ", {} commit{} since", self.commits_since, plural)?;
+        }
+
+        if self.dirty {
+            write!(f, ", uncommitted changes")?;
+        }
+
+        Ok(())
+    }
+}
+
 impl Repository {
+    /// Parses a remote url, the web, ssh (scp-like and `ssh://`) and git forms are supported as well as the npm
+    /// shorthands (e.g. `gitlab:group/repo`)
+    ///
+    /// e.g. git@gitlab.example.org:group/subgroup/repo.git -> **https://gitlab.example.org/group/subgroup/repo**
     pub fn new(url: String) -> Self {
         let url = trim(url).unwrap();
-        // check that the url is in the https|http://platform/user/name form
-        let regex =
-            regex::Regex::new(r"^(git@[^:/]+:[^/]+/[^.]+\.git|https?://[^/]+/[^/]+/[^/]+)$")
-                .unwrap();
-        if !regex.is_match(&url) {
-            return Self {
-                url,
-                name: None,
-                sign: None,
-                platform: RepositoryPlatform::Unknown,
-            };
+
+        let unknown = Self {
+            url: url.clone(),
+            host: None,
+            name: None,
+            sign: None,
+            platform: RepositoryPlatform::Unknown,
+        };
+
+        let remote = url.trim_start_matches("git+").trim_end_matches('/');
+        let remote = remote.strip_suffix(".git").unwrap_or(remote);
+
+        // scheme://[user@]host[:port]/path or [user@]host:path
+        let remote_regex = regex::Regex::new(
+            r"^(?:(?P<scheme>[a-z+]+)://(?:[^@/]+@)?(?P<host>[^/:]+)(?::(?P<port>\d+))?/|(?:[^@/]+@)?(?P<scp_host>[^/:]+):/?)(?P<path>.+)$",
+        )
+        .unwrap();
+
+        let (host, path) = match remote.split_once(':') {
+            // the npm shorthands name the platform instead of the host
+            Some((shorthand @ ("github" | "gitlab" | "bitbucket"), path)) => {
+                let host = match shorthand {
+                    "bitbucket" => "bitbucket.org".to_string(),
+                    _ => format!("{}.com", shorthand),
+                };
+                (host, path.to_string())
+            }
+            _ => {
+                let captures = match remote_regex.captures(remote) {
+                    Some(captures) => captures,
+                    None => return unknown,
+                };
+
+                // a local repository (eg. `file:///srv/repo.git` or `C:/repo`) isn't published anywhere
+                let local = remote.starts_with("file:")
+                    || captures
+                        .name("scp_host")
+                        .is_some_and(|h| h.as_str().len() == 1);
+                if local {
+                    return unknown;
+                }
+
+                let host = captures
+                    .name("host")
+                    .or(captures.name("scp_host"))
+                    .unwrap()
+                    .as_str()
+                    .to_lowercase();
+
+                // the port of a web url is part of the address, the one of an ssh url is not
+                let host = match (
+                    captures.name("scheme").map(|s| s.as_str()),
+                    captures.name("port"),
+                ) {
+                    (Some("http" | "https"), Some(port)) => format!("{}:{}", host, port.as_str()),
+                    _ => host,
+                };
+
+                (host, captures["path"].to_string())
+            }
+        };
+
+        let platform = match RepositoryPlatform::from_str(&host).unwrap() {
+            RepositoryPlatform::Unknown => RepositoryPlatform::SelfHosted,
+            platform => platform,
+        };
+
+        let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+
+        // GitLab and unknown hosts allow nested groups, the other platforms have an owner and a name only
+        let segments: Vec<&str> = match platform {
+            RepositoryPlatform::Gitlab | RepositoryPlatform::SelfHosted => segments
+                .into_iter()
+                .take_while(|segment| *segment != "-")
+                .collect(),
+            _ => segments.into_iter().take(2).collect(),
+        };
+
+        if segments.len() < 2 {
+            return unknown;
+        }
+
+        let sign = segments.join("/");
+        let name = segments.last().unwrap().to_string();
+
+        // plain http is kept only when explicitly used
+        let scheme = match url.starts_with("http://") {
+            true => "http",
+            false => "https",
+        };
+
+        Self {
+            url: format!("{}://{}/{}", scheme, host, sign),
+            host: Some(host),
+            sign: Some(sign),
+            name: Some(name),
+            platform,
+        }
+    }
+
+    /// Sets the platform of the repository from the platforms the config tells for the self-hosted instances
+    pub fn set_platform(&mut self, platforms: &HashMap<String, RepositoryPlatform>) {
+        if let Some(platform) = self.host.as_ref().and_then(|host| platforms.get(host)) {
+            self.platform = platform.clone();
         }
+    }
 
-        if url.starts_with("git") {
-            let url_split = url.split(':').collect::<Vec<&str>>();
-            let sign = url_split[1..]
-                .join("/")
-                .split(".git")
-                .collect::<Vec<&str>>()[0]
-                .to_string();
-
-            let name = sign
-                .split('/')
-                .collect::<Vec<&str>>()
-                .last()
-                .unwrap_or(&"")
-                .to_string();
-
-            let platform_str = url_split[0].split('@').collect::<Vec<&str>>()[1];
-            let platform = RepositoryPlatform::from_str(platform_str).unwrap();
-
-            Self {
-                url: format!("https://{}.com/{}", platform.to_string(), sign),
-                sign: Some(sign),
-                name: Some(name),
-                platform,
+    /// Opens the git repository containing the project, the project may be a subdirectory of the repository, a
+    /// worktree or a bare repository
+    pub fn discover(project_location: &str) -> Result<GitRepository, Error> {
+        Ok(GitRepository::discover(project_location)?)
+    }
+
+    /// Returns the location of the .git folder of the repository, which may be above the project
+    pub fn git_dir(repo: &GitRepository) -> String {
+        repo.path()
+            .to_string_lossy()
+            .trim_end_matches('/')
+            .to_string()
+    }
+
+    /// Returns the name of the remote the project is published to: the given one when the repository has it,
+    /// otherwise `upstream`, `origin` or the only remote of the repository, in this order
+    pub fn remote(repo: &GitRepository, preferred: Option<&str>) -> Option<String> {
+        let remotes: Vec<String> = repo
+            .remotes()
+            .ok()?
+            .iter()
+            .flatten()
+            .map(|remote| remote.to_string())
+            .collect();
+
+        if let Some(preferred) = preferred {
+            if remotes.iter().any(|remote| remote == preferred) {
+                return Some(preferred.to_string());
             }
-        } else {
-            let url_split = url.split('/').collect::<Vec<&str>>();
-            let sign = url_split[3..]
-                .join("/")
-                .split(".git")
-                .collect::<Vec<&str>>()[0]
-                .to_string();
-            let name = sign
-                .split('/')
-                .collect::<Vec<&str>>()
-                .last()
-                .unwrap()
-                .to_string();
-
-            let platform_str = url_split[2];
-            let platform = RepositoryPlatform::from_str(platform_str).unwrap();
-
-            Self {
-                url: url.split(".git").collect::<Vec<&str>>()[0].to_string(),
-                sign: Some(sign),
-                name: Some(name),
-                platform,
+            dialoguer::error("The repository has no remote named {}", &preferred);
+        }
+
+        ["upstream", "origin"]
+            .iter()
+            .map(|name| name.to_string())
+            .find(|name| remotes.contains(name))
+            .or_else(|| match remotes.len() {
+                1 => remotes.first().cloned(),
+                _ => None,
+            })
+    }
+
+    /// Returns the branch the links to the files of the repository should point to, that is the one the HEAD of the
+    /// remote points to, the checked out branch when the remote doesn't tell it, or the `init.defaultBranch` of the
+    /// git config when nothing is checked out
+    pub fn default_branch(repo: &GitRepository, remote: Option<&str>) -> Option<String> {
+        if let Some(remote) = remote {
+            let prefix = format!("refs/remotes/{}/", remote);
+
+            if let Some(branch) = repo
+                .find_reference(&format!("{}HEAD", prefix))
+                .ok()
+                .and_then(|reference| reference.symbolic_target().map(|t| t.to_string()))
+                .and_then(|target| target.strip_prefix(&prefix).map(|b| b.to_string()))
+            {
+                return Some(branch);
             }
         }
+
+        // the head of a new repository points to a branch without commits, its name is still the right one
+        if let Some(branch) = repo
+            .find_reference("HEAD")
+            .ok()
+            .and_then(|head| head.symbolic_target().map(|t| t.to_string()))
+            .and_then(|target| target.strip_prefix("refs/heads/").map(|b| b.to_string()))
+        {
+            return Some(branch);
+        }
+
+        repo.config()
+            .and_then(|config| config.get_string("init.defaultBranch"))
+            .ok()
     }
 
     /// Returns a ConverterOutput struct with the data found in the .git folder
-    pub fn scan(project_location: &str) -> Result<ConverterOutput, Error> {
+    ///
+    /// The authors and co-authors of the commits are replaced by their canonical identity in the given mailmap, the
+    /// ones matching the exclude patterns of the config (e.g. bots) are left out
+    ///
+    /// The remote is chosen as described in [`Repository::remote`], the repository has no url without one
+    pub fn scan(
+        project_location: &str,
+        remote: Option<&str>,
+        mailmap: &Mailmap,
+        config: &ContributorsConfig,
+    ) -> Result<ConverterOutput, Error> {
         let mut git_converter = ConverterOutput::empty();
 
-        git_converter.source_config_file_path = format!("{}.git", project_location);
-
         // Open the repository
-        let repo: GitRepository = match GitRepository::open(project_location) {
+        let repo: GitRepository = match Repository::discover(project_location) {
             Ok(repo) => repo,
             Err(e) => {
                 dialoguer::error("Failed to open repository: {}", &e);
@@ -109,22 +457,49 @@ impl Repository {
             }
         };
 
-        let url: String = repo
-            .find_remote("origin")
-            .unwrap()
-            .url()
-            .unwrap_or(&"".to_string())
-            .to_string();
+        git_converter.source_config_file_path = Repository::git_dir(&repo);
 
-        let project_repository = Repository::new(url);
-        git_converter.repository = Option::from(project_repository.clone());
+        // the tags are available whatever the platform hosting the repository
+        if let Some(version_tag) = VersionTag::scan(&repo) {
+            git_converter.version = Some(version_tag.version.clone());
+            git_converter.version_tag = Some(version_tag);
+        }
+
+        let remote = Repository::remote(&repo, remote);
+
+        git_converter.default_branch = Repository::default_branch(&repo, remote.as_deref());
 
-        git_converter.name = project_repository.name.clone();
+        // a freshly initialized repository has no remote yet, its contributors are still worth collecting
+        let url: Option<String> = remote
+            .and_then(|remote| repo.find_remote(&remote).ok())
+            .and_then(|remote| remote.url().map(|url| url.to_string()));
 
-        // check if the repo is a github repo
-        // if so not need to continue
-        if project_repository.platform == RepositoryPlatform::Github {
-            return Ok(git_converter);
+        // the submodules and the LFS files need extra steps to clone the repository, whatever the platform
+        let submodules = Submodule::scan(&repo, url.as_deref());
+        if !submodules.is_empty() {
+            git_converter.submodules = Some(submodules);
+        }
+
+        let lfs = lfs_patterns(&repo);
+        if !lfs.is_empty() {
+            git_converter.lfs_patterns = Some(lfs);
+        }
+
+        // a local remote (eg. a path or `file://`) doesn't publish the repository, so it can't be cloned from its url
+        let project_repository = url
+            .map(Repository::new)
+            .filter(|repository| repository.platform != RepositoryPlatform::Unknown);
+
+        if let Some(project_repository) = project_repository {
+            git_converter.repository = Option::from(project_repository.clone());
+
+            git_converter.name = project_repository.name.clone();
+
+            // check if the repo is a github repo
+            // if so not need to continue
+            if project_repository.platform == RepositoryPlatform::Github {
+                return Ok(git_converter);
+            }
         }
 
         // Get the head commit
@@ -155,9 +530,9 @@ impl Repository {
 
         revwalk.push(head_commit.id()).unwrap();
 
-        let mut contributors: HashMap<Contributor, i32> = std::collections::HashMap::new();
+        let mut contributors: Vec<Contributor> = vec![];
 
-        // fill contributors hashmap counting the number of commits for each contributor
+        // collect the activity of each contributor, the identities of a same person are counted together
         for oid in revwalk {
             let oid = match oid {
                 Ok(oid) => oid,
@@ -179,27 +554,167 @@ impl Repository {
             let name = author.name().unwrap_or(&"");
             let email = author.email().unwrap_or(&"");
 
-            let contributor = Contributor {
-                name: Some(name.to_string()),
-                email: Some(email.to_string()),
-                url: None,
-            };
+            let date = chrono::NaiveDateTime::from_timestamp_opt(author.when().seconds(), 0)
+                .map(|date| date.format("%Y-%m-%d").to_string())
+                .unwrap_or_default();
+            let (additions, deletions) = Repository::line_changes(&repo, &commit);
+            let stats = ContributorStats::new(&date, additions, deletions);
+
+            // the co-authors share the credit of the commit with its author
+            let mut authors = vec![(name.to_string(), email.to_string())];
+            authors.extend(Repository::co_authors(commit.message().unwrap_or("")));
+
+            for (name, email) in authors.into_iter().unique() {
+                let contributor = mailmap.resolve(&Contributor {
+                    name: Some(name),
+                    email: Some(email),
+                    url: None,
+                    stats: None,
+                });
+
+                let excluded = [&contributor.name, &contributor.email]
+                    .iter()
+                    .any(|value| value.as_ref().is_some_and(|v| config.excludes(v)));
+
+                if excluded {
+                    continue;
+                }
 
-            let count = contributors.entry(contributor).or_insert(0);
-            *count += 1;
+                match contributors
+                    .iter_mut()
+                    .find(|c| c.same_person(&contributor))
+                {
+                    Some(person) => match person.stats.as_mut() {
+                        Some(person_stats) => person_stats.combine(&stats),
+                        None => person.stats = Some(stats.clone()),
+                    },
+                    None => contributors.push(Contributor {
+                        stats: Some(stats.clone()),
+                        ..contributor
+                    }),
+                }
+            }
         }
 
         // sort contributors by number of commits
         let contributors: Contributors = contributors
-            .iter()
-            .sorted_by(|a, b| b.1.cmp(a.1))
-            .map(|(contributor, _)| contributor.clone())
+            .into_iter()
+            .sorted_by_key(|c| std::cmp::Reverse(c.stats.as_ref().map(|s| s.commits)))
             .collect();
 
         git_converter.contributors = Option::from(contributors);
 
         Ok(git_converter)
     }
+
+    /// Returns the name and email of the co-authors credited in the trailers of a commit message, e.g.
+    ///
+    /// ```text
+    /// Co-authored-by: Jane Doe <jane@example.com>
+    /// ```
+    fn co_authors(message: &str) -> Vec<(String, String)> {
+        let trailer = regex::Regex::new(r"(?im)^co-authored-by:\s*(.+?)\s*<([^>]+)>\s*$").unwrap();
+
+        trailer
+            .captures_iter(message)
+            .map(|captures| (captures[1].to_string(), captures[2].trim().to_string()))
+            .collect()
+    }
+
+    /// Returns the lines added and removed by the given commit, nothing is counted for the merge commits
+    fn line_changes(repo: &GitRepository, commit: &git2::Commit) -> (usize, usize) {
+        if commit.parent_count() > 1 {
+            return (0, 0);
+        }
+
+        // the root commit is compared to an empty tree
+        let parent_tree = commit.parent(0).and_then(|parent| parent.tree()).ok();
+
+        commit
+            .tree()
+            .and_then(|tree| repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), None))
+            .and_then(|diff| diff.stats())
+            .map(|stats| (stats.insertions(), stats.deletions()))
+            .unwrap_or((0, 0))
+    }
+
+    /// Returns the page of the given commit on the platform hosting the repository
+    pub fn commit_url(&self, hash: &str) -> Option<String> {
+        match self.platform {
+            RepositoryPlatform::Github
+            | RepositoryPlatform::Gitea
+            | RepositoryPlatform::Sourcehut => Some(format!("{}/commit/{}", self.url, hash)),
+            RepositoryPlatform::Gitlab | RepositoryPlatform::SelfHosted => {
+                Some(format!("{}/-/commit/{}", self.url, hash))
+            }
+            RepositoryPlatform::Bitbucket => Some(format!("{}/commits/{}", self.url, hash)),
+            _ => None,
+        }
+    }
+
+    /// Returns the page listing the changes between two revisions on the platform hosting the repository
+    pub fn compare_url(&self, from: &str, to: &str) -> Option<String> {
+        match self.platform {
+            RepositoryPlatform::Github | RepositoryPlatform::Gitea => {
+                Some(format!("{}/compare/{}...{}", self.url, from, to))
+            }
+            RepositoryPlatform::Gitlab | RepositoryPlatform::SelfHosted => {
+                Some(format!("{}/-/compare/{}...{}", self.url, from, to))
+            }
+            RepositoryPlatform::Bitbucket => {
+                Some(format!("{}/branches/compare/{}%0D{}", self.url, to, from))
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the page of the given file of the repository on the given branch
+    pub fn blob_url(&self, branch: &str, file: &str) -> Option<String> {
+        let file = file.trim_start_matches('/');
+
+        match self.platform {
+            RepositoryPlatform::Github => Some(format!("{}/blob/{}/{}", self.url, branch, file)),
+            RepositoryPlatform::Gitlab | RepositoryPlatform::SelfHosted => {
+                Some(format!("{}/-/blob/{}/{}", self.url, branch, file))
+            }
+            RepositoryPlatform::Bitbucket => Some(format!("{}/src/{}/{}", self.url, branch, file)),
+            RepositoryPlatform::Gitea => {
+                Some(format!("{}/src/branch/{}/{}", self.url, branch, file))
+            }
+            RepositoryPlatform::Sourcehut => {
+                Some(format!("{}/tree/{}/item/{}", self.url, branch, file))
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the issue tracker of the repository, sourcehut keeps it on a separate service
+    pub fn issues_url(&self) -> Option<String> {
+        match self.platform {
+            RepositoryPlatform::Github
+            | RepositoryPlatform::Bitbucket
+            | RepositoryPlatform::Gitea => Some(format!("{}/issues", self.url)),
+            RepositoryPlatform::Gitlab | RepositoryPlatform::SelfHosted => {
+                Some(format!("{}/-/issues", self.url))
+            }
+            RepositoryPlatform::Sourcehut => {
+                Some(format!("https://todo.sr.ht/{}", self.sign.as_ref()?))
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the page charting the contributions to the given branch of the repository
+    pub fn contributors_url(&self, branch: &str) -> Option<String> {
+        match self.platform {
+            RepositoryPlatform::Github => Some(format!("{}/graphs/contributors", self.url)),
+            RepositoryPlatform::Gitlab | RepositoryPlatform::SelfHosted => {
+                Some(format!("{}/-/graphs/{}", self.url, branch))
+            }
+            RepositoryPlatform::Gitea => Some(format!("{}/activity/contributors", self.url)),
+            _ => None,
+        }
+    }
 }
 
 impl GenMarkdown for Repository {
@@ -211,11 +726,8 @@ impl GenMarkdown for Repository {
             .register_template_string("contrib_rocks_tpl", contrib_rocks_tpl)
             .unwrap();
 
-        let repo_url = self.url.clone();
-        let repo_contrib_url = format!(
-            "{}/graphs/contributors",
-            repo_url.split(".git").collect::<Vec<&str>>()[0]
-        );
+        // contrib.rocks is for GitHub repositories only, the default branch doesn't matter there
+        let repo_contrib_url = self.contributors_url("").unwrap_or(self.url.clone());
 
         let data: Value = json!({
             "repository_contrib_url": repo_contrib_url,
@@ -228,10 +740,17 @@ impl GenMarkdown for Repository {
 
 // possible repository platforms
 #[derive(Debug, Clone, EnumIter, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
 pub enum RepositoryPlatform {
     Github,
     Gitlab,
     Bitbucket,
+    /// Gitea and its Forgejo fork, e.g. codeberg.org
+    Gitea,
+    /// sourcehut, e.g. git.sr.ht
+    Sourcehut,
+    /// A host not recognized, its pages are expected at the same paths as on GitLab, the most common self-hosted
+    /// platform, unless the config tells the platform of the host
     SelfHosted,
     Unknown,
 }
@@ -242,6 +761,8 @@ impl RepositoryPlatform {
             RepositoryPlatform::Github => "github",
             RepositoryPlatform::Gitlab => "gitlab",
             RepositoryPlatform::Bitbucket => "bitbucket",
+            RepositoryPlatform::Gitea => "gitea",
+            RepositoryPlatform::Sourcehut => "sourcehut",
             RepositoryPlatform::SelfHosted => "self-hosted",
             RepositoryPlatform::Unknown => "unknown",
         }
@@ -257,11 +778,17 @@ impl PartialEq<str> for RepositoryPlatform {
 impl FromStr for RepositoryPlatform {
     type Err = ();
 
+    /// Recognizes the platform from the host serving the repository, self-hosted instances usually have the name
+    /// of the software in their host (eg. gitlab.example.org)
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
             s if s.contains("github") => Ok(RepositoryPlatform::Github),
             s if s.contains("gitlab") => Ok(RepositoryPlatform::Gitlab),
             s if s.contains("bitbucket") => Ok(RepositoryPlatform::Bitbucket),
+            s if s.contains("gitea") || s.contains("forgejo") || s.contains("codeberg.org") => {
+                Ok(RepositoryPlatform::Gitea)
+            }
+            s if s.ends_with("sr.ht") => Ok(RepositoryPlatform::Sourcehut),
             _ => Ok(RepositoryPlatform::Unknown),
         }
     }
@@ -273,8 +800,143 @@ impl ToString for RepositoryPlatform {
             RepositoryPlatform::Github => "github".to_string(),
             RepositoryPlatform::Gitlab => "gitlab".to_string(),
             RepositoryPlatform::Bitbucket => "bitbucket".to_string(),
+            RepositoryPlatform::Gitea => "gitea".to_string(),
+            RepositoryPlatform::Sourcehut => "sourcehut".to_string(),
             RepositoryPlatform::SelfHosted => "self-hosted".to_string(),
             RepositoryPlatform::Unknown => "unknown".to_string(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_scp_remote() {
+        let repository = Repository::new("git@github.com:acme/widgets.git".to_string());
+
+        assert_eq!(repository.url, "https://github.com/acme/widgets");
+        assert_eq!(repository.sign.as_deref(), Some("acme/widgets"));
+        assert_eq!(repository.name.as_deref(), Some("widgets"));
+        assert_eq!(repository.platform, RepositoryPlatform::Github);
+    }
+
+    #[test]
+    fn parses_ssh_remote_without_its_port() {
+        let repository =
+            Repository::new("ssh://git@gitlab.example.org:2222/group/repo.git".to_string());
+
+        assert_eq!(repository.url, "https://gitlab.example.org/group/repo");
+        assert_eq!(repository.host.as_deref(), Some("gitlab.example.org"));
+        assert_eq!(repository.platform, RepositoryPlatform::Gitlab);
+    }
+
+    #[test]
+    fn keeps_the_subgroups_of_gitlab() {
+        let repository =
+            Repository::new("https://gitlab.com/group/subgroup/repo/-/tree/main".to_string());
+
+        assert_eq!(repository.url, "https://gitlab.com/group/subgroup/repo");
+        assert_eq!(repository.sign.as_deref(), Some("group/subgroup/repo"));
+        assert_eq!(repository.name.as_deref(), Some("repo"));
+    }
+
+    #[test]
+    fn parses_sourcehut_remote() {
+        let repository = Repository::new("https://git.sr.ht/~acme/widgets".to_string());
+
+        assert_eq!(repository.sign.as_deref(), Some("~acme/widgets"));
+        assert_eq!(repository.platform, RepositoryPlatform::Sourcehut);
+        assert_eq!(
+            repository.issues_url().as_deref(),
+            Some("https://todo.sr.ht/~acme/widgets")
+        );
+    }
+
+    #[test]
+    fn parses_npm_shorthand() {
+        let repository = Repository::new("gitlab:group/repo".to_string());
+
+        assert_eq!(repository.url, "https://gitlab.com/group/repo");
+        assert_eq!(repository.platform, RepositoryPlatform::Gitlab);
+    }
+
+    #[test]
+    fn keeps_the_port_of_self_hosted_web_remote() {
+        let repository = Repository::new("https://git.example.org:8443/team/repo.git".to_string());
+
+        assert_eq!(repository.url, "https://git.example.org:8443/team/repo");
+        assert_eq!(repository.platform, RepositoryPlatform::SelfHosted);
+        assert_eq!(
+            repository.blob_url("main", "LICENSE").as_deref(),
+            Some("https://git.example.org:8443/team/repo/-/blob/main/LICENSE")
+        );
+    }
+
+    #[test]
+    fn ignores_local_remotes() {
+        for url in [
+            "file:///srv/repo.git",
+            "/srv/repo.git",
+            "../repo",
+            "C:/work/repo",
+        ] {
+            let repository = Repository::new(url.to_string());
+
+            assert_eq!(repository.platform, RepositoryPlatform::Unknown, "{}", url);
+            assert!(repository.sign.is_none(), "{}", url);
+        }
+    }
+
+    #[test]
+    fn scan_leaves_out_local_remotes() {
+        let location =
+            std::env::temp_dir().join(format!("writeme-local-remote-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&location);
+        let repo = GitRepository::init(&location).unwrap();
+        repo.remote("origin", "/srv/repo.git").unwrap();
+
+        let output = Repository::scan(
+            location.to_str().unwrap(),
+            None,
+            &Mailmap::default(),
+            &ContributorsConfig::default(),
+        )
+        .unwrap();
+        std::fs::remove_dir_all(&location).unwrap();
+
+        assert!(output.repository.is_none());
+        assert!(output.name.is_none());
+    }
+
+    #[test]
+    fn resolves_relative_submodule_urls() {
+        let cases = [
+            (
+                "https://github.com/acme/app.git",
+                "../lib.git",
+                "https://github.com/acme/lib.git",
+            ),
+            (
+                "git@github.com:acme/app.git",
+                "../lib.git",
+                "git@github.com:acme/lib.git",
+            ),
+            (
+                "git@github.com:acme/app",
+                "./vendor/lib",
+                "git@github.com:acme/app/vendor/lib",
+            ),
+            (
+                "ssh://git@example.org/srv/app.git/",
+                "../../lib.git",
+                "ssh://git@example.org/lib.git",
+            ),
+        ];
+
+        for (remote_url, relative, expected) in cases {
+            assert_eq!(Submodule::resolve_url(remote_url, relative), expected);
+        }
+    }
+}
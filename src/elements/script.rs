@@ -0,0 +1,78 @@
+use std::{fmt::Display, path::Path};
+
+use serde::Serialize;
+use serde_json::Value;
+
+/// A runnable task of the project (eg. a package.json script or a Makefile target)
+#[derive(Debug, Clone, Serialize)]
+pub struct Script {
+    /// The name of the task as declared in the file
+    pub name: String,
+    /// The command to type in the terminal to run the task, e.g. `pnpm build`
+    pub command: String,
+    pub description: Option<String>,
+    /// The location of the file declaring the task in the project structure
+    pub source: String,
+}
+
+/// The package managers able to run the scripts of a package.json
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum PackageManager {
+    Npm,
+    Yarn,
+    Pnpm,
+}
+
+impl PackageManager {
+    /// Detects the package manager from the `packageManager` field of the package.json or, when missing, from
+    /// the lockfile next to it
+    ///
+    /// Reference: https://nodejs.org/api/packages.html#packagemanager
+    pub fn detect(package_json_path: &str, json: &Value) -> Self {
+        if let Some(package_manager) = json["packageManager"].as_str() {
+            match package_manager.split('@').next().unwrap_or("") {
+                "yarn" => return PackageManager::Yarn,
+                "pnpm" => return PackageManager::Pnpm,
+                "npm" => return PackageManager::Npm,
+                _ => {}
+            }
+        }
+
+        let dir = Path::new(package_json_path)
+            .parent()
+            .unwrap_or(Path::new(""));
+
+        if dir.join("pnpm-lock.yaml").exists() {
+            PackageManager::Pnpm
+        } else if dir.join("yarn.lock").exists() {
+            PackageManager::Yarn
+        } else {
+            PackageManager::Npm
+        }
+    }
+
+    /// Returns the command running the given script with this package manager
+    pub fn run(&self, script: &str) -> String {
+        match self {
+            // npm needs `run` except for the scripts having a dedicated command
+            PackageManager::Npm => match script {
+                "start" | "test" | "stop" | "restart" => format!("npm {}", script),
+                _ => format!("npm run {}", script),
+            },
+            PackageManager::Yarn => format!("yarn {}", script),
+            PackageManager::Pnpm => format!("pnpm {}", script),
+        }
+    }
+}
+
+impl Display for PackageManager {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            PackageManager::Npm => "npm",
+            PackageManager::Yarn => "yarn",
+            PackageManager::Pnpm => "pnpm",
+        };
+
+        write!(f, "{}", name)
+    }
+}
@@ -0,0 +1,136 @@
+use std::{fs, path::Path};
+
+use anyhow::Error;
+use serde::Serialize;
+
+use crate::{config::StructureConfig, converter::ConverterOutput};
+
+/// A directory shown in the project structure tree
+#[derive(Debug, Clone, Serialize)]
+pub struct Directory {
+    /// The path of the directory relative to the project root, e.g. `src/converter`
+    pub path: String,
+    /// What the directory contains, from the config file or the first line of the directory README
+    pub description: Option<String>,
+}
+
+impl Directory {
+    /// Returns the name of the directory, the last component of its path
+    pub fn name(&self) -> &str {
+        self.path.rsplit('/').next().unwrap_or(&self.path)
+    }
+
+    /// Collects the directories of the project up to the configured depth, the hidden ones are left out
+    ///
+    /// The paths come from the project search, so the ignored and blacklisted directories are already out
+    pub fn scan(
+        paths: &[String],
+        project_location: &str,
+        config: &StructureConfig,
+    ) -> Result<ConverterOutput, Error> {
+        let mut directories: Vec<Directory> = paths
+            .iter()
+            .filter(|path| Path::new(path).is_dir())
+            .filter_map(|path| {
+                let relative = Path::new(path).strip_prefix(project_location).ok()?;
+                let relative = relative.to_string_lossy().replace('\\', "/");
+
+                if relative.is_empty() || relative.split('/').any(|c| c.starts_with('.')) {
+                    return None;
+                }
+
+                Some(relative)
+            })
+            .filter(|relative| relative.split('/').count() <= config.depth)
+            .map(|relative| Directory {
+                description: config.descriptions.get(&relative).cloned().or_else(|| {
+                    Directory::readme_description(&Path::new(project_location).join(&relative))
+                }),
+                path: relative,
+            })
+            .collect();
+
+        // compare the components, a plain string sort would put `src-x` between `src` and `src/a`
+        directories.sort_by(|a, b| a.path.split('/').cmp(b.path.split('/')));
+
+        let mut converter = ConverterOutput::empty();
+        converter.source_config_file_path = project_location.to_string();
+        converter.structure = Some(directories).filter(|d| !d.is_empty());
+
+        Ok(converter)
+    }
+
+    /// Returns the first meaningful line of the README of the directory, headings markers included are removed
+    fn readme_description(dir: &Path) -> Option<String> {
+        let contents = ["README.md", "readme.md", "README"]
+            .iter()
+            .find_map(|name| fs::read_to_string(dir.join(name)).ok())?;
+
+        contents
+            .lines()
+            .map(|line| line.trim().trim_start_matches('#').trim())
+            // badges and html blocks don't describe anything
+            .find(|line| !line.is_empty() && !line.starts_with('<') && !line.starts_with("[!["))
+            .map(|line| line.to_string())
+    }
+
+    /// Renders the directories as a tree, e.g.
+    ///
+    /// ```text
+    /// writeme/
+    /// ├── conf/  # templates and catalogs
+    /// └── src/
+    ///     └── converter/
+    /// ```
+    pub fn tree(root: &str, directories: &[Directory]) -> String {
+        let mut lines: Vec<(String, Option<&String>)> = vec![(format!("{}/", root), None)];
+
+        for (i, directory) in directories.iter().enumerate() {
+            let mut prefix = String::new();
+
+            // an ancestor which is the last of its siblings doesn't need a vertical line
+            let ancestors: Vec<&str> = directory.path.split('/').collect();
+            for depth in 1..ancestors.len() {
+                let ancestor = ancestors[..depth].join("/");
+                let last = Directory::is_last(&ancestor, &directories[i..]);
+                prefix.push_str(if last { "    " } else { "│   " });
+            }
+
+            let last = Directory::is_last(&directory.path, &directories[i..]);
+            prefix.push_str(if last { "└── " } else { "├── " });
+
+            lines.push((
+                format!("{}{}/", prefix, directory.name()),
+                directory.description.as_ref(),
+            ));
+        }
+
+        let width = lines
+            .iter()
+            .filter(|(_, d)| d.is_some())
+            .map(|(l, _)| l.chars().count())
+            .max()
+            .unwrap_or(0);
+
+        lines
+            .into_iter()
+            .map(|(line, description)| match description {
+                Some(description) => {
+                    let padding = width - line.chars().count();
+                    format!("{}{}  # {}", line, " ".repeat(padding), description)
+                }
+                None => line,
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    /// Whether no sibling of the given directory comes after it in the remaining directories
+    fn is_last(path: &str, remaining: &[Directory]) -> bool {
+        let parent = |path: &str| path.rsplit_once('/').map(|(p, _)| p.to_string());
+
+        !remaining
+            .iter()
+            .any(|d| d.path != path && parent(&d.path) == parent(path))
+    }
+}
@@ -0,0 +1,179 @@
+use std::{fmt::Display, fs, path::Path};
+
+use anyhow::Error;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::converter::ConverterOutput;
+
+/// A tool that must be installed before running the project, pinned by a version file or required by a manifest
+#[derive(Debug, Clone, Serialize)]
+pub struct Prerequisite {
+    /// The identifier of the tool, e.g. `node` or `rust`
+    pub tool: String,
+    /// The version as written in the source, either an exact version (`18.17.0`) or a range (`>=18`)
+    pub version: String,
+    /// Whether the version is the oldest supported one rather than the one to install (eg. Cargo `rust-version`)
+    pub minimum: bool,
+    /// Whether the version comes from a pin file read by the version managers (eg. .nvmrc) rather than a manifest
+    pub pinned: bool,
+    /// The location of the file declaring the version in the project structure
+    pub source: String,
+    /// The version pinned by a pin file next to the minimum one of a manifest, e.g. the channel of
+    /// rust-toolchain.toml next to the Cargo `rust-version`
+    pub pin: Option<String>,
+}
+
+impl Prerequisite {
+    pub fn new(tool: &str, version: &str, source: &str) -> Self {
+        Prerequisite {
+            tool: tool.to_string(),
+            // version managers accept both `v18.17.0` and `18.17.0`
+            version: version.trim().trim_start_matches('v').to_string(),
+            minimum: false,
+            pinned: false,
+            source: source.to_string(),
+            pin: None,
+        }
+    }
+
+    /// Returns the display name of the tool
+    pub fn name(&self) -> String {
+        match self.tool.as_str() {
+            "node" => "Node.js",
+            "npm" => "npm",
+            "yarn" => "Yarn",
+            "pnpm" => "pnpm",
+            "bun" => "Bun",
+            "deno" => "Deno",
+            "rust" => "Rust",
+            "python" => "Python",
+            "php" => "PHP",
+            "ruby" => "Ruby",
+            "go" => "Go",
+            "java" => "Java",
+            tool => tool,
+        }
+        .to_string()
+    }
+
+    /// Returns the page explaining how to install the tool, if known
+    pub fn install_url(&self) -> Option<&'static str> {
+        match self.tool.as_str() {
+            "node" => Some("https://nodejs.org/en/download"),
+            "npm" => Some("https://docs.npmjs.com/downloading-and-installing-node-js-and-npm"),
+            "yarn" => Some("https://yarnpkg.com/getting-started/install"),
+            "pnpm" => Some("https://pnpm.io/installation"),
+            "bun" => Some("https://bun.sh/docs/installation"),
+            "deno" => Some("https://docs.deno.com/runtime/getting_started/installation/"),
+            "rust" => Some("https://www.rust-lang.org/tools/install"),
+            "python" => Some("https://www.python.org/downloads/"),
+            "php" => Some("https://www.php.net/downloads"),
+            "ruby" => Some("https://www.ruby-lang.org/en/downloads/"),
+            "go" => Some("https://go.dev/doc/install"),
+            "java" => Some("https://adoptium.net/"),
+            _ => None,
+        }
+    }
+
+    /// Scan the project for the toolchain pin files and return a list of converter output
+    /// each converter output will contain the tools pinned by one file
+    pub fn scan(paths: &Vec<String>) -> Result<Vec<ConverterOutput>, Error> {
+        let mut converter_outputs: Vec<ConverterOutput> = vec![];
+
+        for path in paths {
+            let file_name = match Path::new(path).file_name().and_then(|f| f.to_str()) {
+                Some(file_name) => file_name,
+                None => continue,
+            };
+
+            let contents = match file_name {
+                ".nvmrc"
+                | ".node-version"
+                | ".tool-versions"
+                | ".python-version"
+                | "rust-toolchain"
+                | "rust-toolchain.toml" => match fs::read_to_string(path) {
+                    Ok(contents) => contents,
+                    Err(_) => continue,
+                },
+                _ => continue,
+            };
+
+            let prerequisites: Vec<Prerequisite> = Prerequisite::parse(file_name, &contents, path)
+                .into_iter()
+                .map(|p| Prerequisite { pinned: true, ..p })
+                .collect();
+            if prerequisites.is_empty() {
+                continue;
+            }
+
+            let mut converter = ConverterOutput::empty();
+            converter.source_config_file_path = path.to_string();
+            converter.prerequisites = Some(prerequisites);
+
+            converter_outputs.push(converter);
+        }
+
+        Ok(converter_outputs)
+    }
+
+    /// Returns the tools pinned in the given pin file contents
+    fn parse(file_name: &str, contents: &str, path: &str) -> Vec<Prerequisite> {
+        // the pin files may contain comments, the version is the first meaningful line
+        let first_line = contents
+            .lines()
+            .map(|l| l.split('#').next().unwrap_or("").trim())
+            .find(|l| !l.is_empty());
+
+        match file_name {
+            ".nvmrc" | ".node-version" => first_line
+                .map(|v| vec![Prerequisite::new("node", v, path)])
+                .unwrap_or_default(),
+            ".python-version" => first_line
+                .map(|v| vec![Prerequisite::new("python", v, path)])
+                .unwrap_or_default(),
+            // the legacy file only contains the channel, e.g. `1.72.0` or `nightly-2023-08-01`
+            "rust-toolchain" if !contents.contains('[') => first_line
+                .map(|v| vec![Prerequisite::new("rust", v, path)])
+                .unwrap_or_default(),
+            "rust-toolchain" | "rust-toolchain.toml" => toml::from_str::<Value>(contents)
+                .ok()
+                .and_then(|t| t["toolchain"]["channel"].as_str().map(|c| c.to_string()))
+                .map(|c| vec![Prerequisite::new("rust", &c, path)])
+                .unwrap_or_default(),
+            // asdf lists a tool per line, e.g. `nodejs 18.17.0`, the first version is the preferred one
+            // https://asdf-vm.com/manage/configuration.html#tool-versions
+            ".tool-versions" => contents
+                .lines()
+                .map(|l| l.split('#').next().unwrap_or("").trim())
+                .filter_map(|l| {
+                    let mut parts = l.split_whitespace();
+                    let tool = match parts.next()? {
+                        "nodejs" => "node",
+                        "golang" => "go",
+                        tool => tool,
+                    };
+
+                    Some(Prerequisite::new(tool, parts.next()?, path))
+                })
+                .collect(),
+            _ => vec![],
+        }
+    }
+}
+
+impl Display for Prerequisite {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.minimum {
+            write!(f, "{} {} or newer", self.name(), self.version)?;
+        } else {
+            write!(f, "{} {}", self.name(), self.version)?;
+        }
+
+        match &self.pin {
+            Some(pin) => write!(f, " (pinned to {})", pin),
+            None => Ok(()),
+        }
+    }
+}
@@ -1,4 +1,5 @@
 mod assembler;
+mod config;
 mod converter;
 mod dialoguer;
 mod elements;
@@ -8,7 +9,12 @@ mod utils;
 
 use assembler::Assembler;
 use clap::Parser;
-use elements::{license::License, repository::Repository};
+use config::Config;
+use elements::{
+    changelog::Release, ci::Workflow, env::EnvVar, language::Language, license::License,
+    lockfile::Lockfile, mailmap::Mailmap, repository::Repository, structure::Directory,
+    toolchain::Prerequisite,
+};
 use std::path::Path;
 use utils::Project;
 
@@ -20,10 +26,14 @@ struct Args {
     /// Path to the project to scan
     #[arg(short, long, default_value = "./")]
     path: String,
+
+    /// Remote the project is published to, by default upstream, origin or the only remote of the repository
+    #[arg(short, long)]
+    remote: Option<String>,
 }
 
 /// Method used to Scan the project merges the data found and assembles it to create a README file
-fn writeme(project_location: &str) {
+fn writeme(project_location: &str, remote: Option<&str>) {
     let project: Project = match Project::load(project_location) {
         Ok(project) => project,
         Err(e) => {
@@ -32,6 +42,16 @@ fn writeme(project_location: &str) {
         }
     };
 
+    let config: Config = match Config::load(project_location) {
+        Ok(config) => config,
+        Err(e) => {
+            dialoguer::error("Error: Failed to load config: {}", &e);
+            Config::default()
+        }
+    };
+
+    let mailmap = Mailmap::load(project_location);
+
     let converter = converter::Converter::new();
     let merger = merger::Merger::new();
 
@@ -55,10 +75,15 @@ fn writeme(project_location: &str) {
             continue;
         }
 
-        outputs.push(output.unwrap());
+        let mut output = output.unwrap();
+        output.contributors = output
+            .contributors
+            .map(|contributors| mailmap.resolve_all(&contributors));
+
+        outputs.push(output);
     }
 
-    match Repository::scan(project_location) {
+    match Repository::scan(project_location, remote, &mailmap, &config.contributors) {
         Ok(scan_git) => outputs.push(scan_git),
         Err(_) => {} // if unable to scan git do nothing
     };
@@ -72,6 +97,41 @@ fn writeme(project_location: &str) {
         Err(_) => {} // if unable to scan license file do nothing
     };
 
+    // if unable to walk the git history do nothing, the changelog is not generated
+    if let Ok(releases) = Release::scan(project_location) {
+        outputs.push(releases);
+    }
+
+    // if unable to scan lockfiles do nothing, the declared versions are enough
+    if let Ok(lockfiles) = Lockfile::scan(&project.paths) {
+        outputs.extend(lockfiles);
+    }
+
+    // if unable to scan the toolchain pin files do nothing
+    if let Ok(pins) = Prerequisite::scan(&project.paths) {
+        outputs.extend(pins);
+    }
+
+    // if unable to scan the env templates do nothing
+    if let Ok(env_templates) = EnvVar::scan(&project.paths) {
+        outputs.extend(env_templates);
+    }
+
+    // if unable to scan the CI configuration do nothing
+    if let Ok(workflows) = Workflow::scan(&project.paths) {
+        outputs.extend(workflows);
+    }
+
+    // if unable to compute the languages breakdown do nothing
+    if let Ok(languages) = Language::scan(&project.paths, project_location) {
+        outputs.push(languages);
+    }
+
+    // if unable to collect the project structure do nothing
+    if let Ok(structure) = Directory::scan(&project.paths, project_location, &config.structure) {
+        outputs.push(structure);
+    }
+
     let mut merged = match merger.merge(outputs) {
         Ok(merged) => merged,
         Err(e) => {
@@ -80,22 +140,26 @@ fn writeme(project_location: &str) {
         }
     };
 
-    match License::create(
-        project_location,
-        merged.license.as_ref().unwrap(),
-        merged.name.clone(),
-    ) {
-        Ok(output_path) => {
-            if output_path.is_some() {
-                merged.license.as_mut().unwrap().path = output_path
-            }
-        }
+    // the config tells the platform of the self-hosted instances
+    if let Some(repository) = merged.repository.as_mut() {
+        repository.set_platform(&config.repository.platforms);
+    }
+
+    // the copyright goes to the main author, or to the project when the authors are unknown
+    let holder = merged
+        .contributors
+        .as_ref()
+        .and_then(|contributors| contributors.iter().find_map(|c| c.name.clone()))
+        .or(merged.name.clone());
+
+    match License::create(project_location, merged.license.as_mut().unwrap(), holder) {
+        Ok(_) => {}
         Err(e) => {
             dialoguer::error("Error: Failed to create license file: {}", &e);
         }
     }
 
-    match Assembler::new(merged).assemble(project_location, &project.paths) {
+    match Assembler::new(merged, config).assemble(project_location, &project.paths) {
         Ok(_) => {}
         Err(e) => {
             dialoguer::error("Error: Failed to assemble: {}", &e);
@@ -113,6 +177,6 @@ fn main() {
         return;
     }
     dialoguer::hello();
-    writeme(path.to_str().unwrap());
+    writeme(path.to_str().unwrap(), args.remote.as_deref());
     dialoguer::bye();
 }
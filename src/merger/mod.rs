@@ -1,9 +1,20 @@
-use std::fmt::{Debug, Display};
+use std::{
+    fmt::{Debug, Display},
+    path::Path,
+};
 
 use crate::{
-    converter::{ConverterOutput},
+    converter::{Contributor, Converter, ConverterOutput},
     dialoguer::{select_option, SelectOption},
-    elements::{license::{License, SupportedLicense}, repository::Repository},
+    elements::{
+        docker::Docker,
+        env::EnvVar,
+        license::{License, SupportedLicense},
+        lockfile::{Ecosystem, LockedPackage},
+        repository::Repository,
+        script::Script,
+        toolchain::Prerequisite,
+    },
 };
 use anyhow::{Error, Ok};
 use itertools::Itertools;
@@ -63,6 +74,25 @@ impl Merger {
             None,
         );
 
+        output.version_tag = converted_configs
+            .iter()
+            .find_map(|config| config.version_tag.clone());
+
+        // a tag behind the manifests usually means the release is not tagged yet, or the other way around
+        let version_label = output.version_tag.as_ref().and_then(|version_tag| {
+            converted_configs
+                .iter()
+                .filter(|config| config.version_tag.is_none())
+                .filter_map(|config| config.version.as_ref())
+                .any(|version| version.trim_start_matches('v') != version_tag.version)
+                .then(|| {
+                    format!(
+                        "The latest tag {} doesn't match the manifest version, which one do you want in your awesome README?",
+                        version_tag.tag
+                    )
+                })
+        });
+
         output.version = self.merge_field(
             "version",
             converted_configs
@@ -73,6 +103,28 @@ impl Merger {
                 .unique_by(|item| item.version.clone())
                 .map(|config| SelectOption {
                     value: config.version.clone(),
+                    name: match &config.version_tag {
+                        Some(version_tag) => {
+                            format!("{}, {}", config.source_config_file_path, version_tag)
+                        }
+                        None => config.source_config_file_path.clone(),
+                    },
+                })
+                .collect(),
+            version_label,
+        );
+
+        output.rust_version = self.merge_field(
+            "rust-version",
+            converted_configs
+                .iter()
+                .filter(|config| {
+                    config.rust_version.is_some()
+                        && !config.rust_version.as_ref().unwrap().is_empty()
+                })
+                .unique_by(|item| item.rust_version.clone())
+                .map(|config| SelectOption {
+                    value: config.rust_version.clone(),
                     name: config.source_config_file_path.clone(),
                 })
                 .collect(),
@@ -101,21 +153,62 @@ impl Merger {
 
         // don't merge authors, contributors, dependencies, dev_dependencies, build_dependencies, funding
         // but apply a distinct on them, base on each unique property
+        // the same person may appear with different emails or names across the manifests and the git history
         output.contributors = Some(
+            Contributor::merge_identities(
+                converted_configs
+                    .iter()
+                    .flat_map(|config| config.contributors.clone())
+                    .flat_map(|contributors| contributors.iter().cloned().collect::<Vec<_>>()),
+            )
+            .into_iter()
+            .collect(),
+        );
+
+        output.locked_packages = Some(
             converted_configs
                 .iter()
-                .flat_map(|config| config.contributors.clone())
+                .flat_map(|config| config.locked_packages.clone())
                 .flatten()
-                .unique_by(|item| item.email.clone())
+                .unique_by(|item| (item.ecosystem, item.name.clone(), item.version.clone()))
                 .collect(),
         );
 
+        // attach the exact version installed to each declared dependency, from the lockfiles of the ecosystem of
+        // its manifest
+        let locked = output.locked_packages.clone().unwrap_or_default();
+        let converted_configs: Vec<ConverterOutput> = converted_configs
+            .into_iter()
+            .map(|mut config| {
+                let ecosystem = Ecosystem::from_manifest(&config.source_config_file_path);
+
+                for dependencies in [
+                    &mut config.dependencies,
+                    &mut config.dev_dependencies,
+                    &mut config.build_dependencies,
+                ] {
+                    *dependencies = dependencies.take().map(|dependencies| {
+                        dependencies
+                            .map(|mut dependency| {
+                                dependency.resolved = ecosystem.and_then(|ecosystem| {
+                                    LockedPackage::resolve(&dependency, ecosystem, &locked)
+                                });
+                                dependency
+                            })
+                            .collect()
+                    });
+                }
+
+                config
+            })
+            .collect();
+
         output.dependencies = Some(
             converted_configs
                 .iter()
                 .flat_map(|config| config.dependencies.clone())
                 .flatten()
-                .unique_by(|item| item.name.clone())
+                .unique_by(|item| (item.name.clone(), item.target.clone()))
                 .collect(),
         );
 
@@ -124,7 +217,7 @@ impl Merger {
                 .iter()
                 .flat_map(|config| config.dev_dependencies.clone())
                 .flatten()
-                .unique_by(|item| item.name.clone())
+                .unique_by(|item| (item.name.clone(), item.target.clone()))
                 .collect(),
         );
 
@@ -133,7 +226,7 @@ impl Merger {
                 .iter()
                 .flat_map(|config| config.build_dependencies.clone())
                 .flatten()
-                .unique_by(|item| item.name.clone())
+                .unique_by(|item| (item.name.clone(), item.target.clone()))
                 .collect(),
         );
 
@@ -146,11 +239,151 @@ impl Merger {
                 .collect(),
         );
 
+        output.features = Some(
+            converted_configs
+                .iter()
+                .flat_map(|config| config.features.clone())
+                .flatten()
+                .unique_by(|item| item.name.clone())
+                .collect(),
+        );
+
+        output.targets = Some(
+            converted_configs
+                .iter()
+                .flat_map(|config| config.targets.clone())
+                .flatten()
+                .unique_by(|item| (item.kind, item.name.clone()))
+                .collect(),
+        );
+
+        output.workflows = Some(
+            converted_configs
+                .iter()
+                .flat_map(|config| config.workflows.clone())
+                .flatten()
+                .unique_by(|item| (item.file.clone(), item.name.clone()))
+                .sorted_by(|a, b| a.file.cmp(&b.file))
+                .collect(),
+        );
+
+        output.releases = converted_configs
+            .iter()
+            .find_map(|config| config.releases.clone());
+
+        output.structure = converted_configs
+            .iter()
+            .find_map(|config| config.structure.clone());
+
+        output.default_branch = converted_configs
+            .iter()
+            .find_map(|config| config.default_branch.clone());
+
+        output.submodules = converted_configs
+            .iter()
+            .find_map(|config| config.submodules.clone());
+
+        output.lfs_patterns = converted_configs
+            .iter()
+            .find_map(|config| config.lfs_patterns.clone());
+
+        output.languages = converted_configs
+            .iter()
+            .find_map(|config| config.languages.clone());
+
+        // without a manifest the languages breakdown is the only hint of what the project is written in
+        if !converted_configs
+            .iter()
+            .any(|config| Converter::is_manifest(&config.source_config_file_path))
+        {
+            output.primary_language = output
+                .languages
+                .as_ref()
+                .and_then(|languages| languages.first())
+                .map(|language| language.name.clone());
+        }
+
+        output.docker = self.merge_docker(converted_configs.clone());
+
+        output.scripts = self.merge_scripts(
+            converted_configs
+                .iter()
+                .flat_map(|config| config.scripts.clone())
+                .flatten()
+                .collect(),
+        );
+
+        output.test_commands = self.merge_scripts(
+            converted_configs
+                .iter()
+                .flat_map(|config| config.test_commands.clone())
+                .flatten()
+                .collect(),
+        );
+
+        output.prerequisites = self.merge_prerequisites(converted_configs.clone());
+
+        output.env_vars = self.merge_env_vars(converted_configs.clone());
+
         Ok(output)
     }
 
-    fn merge_licenses(&self, converted_configs: Vec<ConverterOutput>) -> Option<License>{
-        let selected:Option<License>;
+    /// Asks the user which license the file of an uncertain license is under, the most similar licenses are proposed
+    fn confirm_license(&self, mut license: License, declared: &[SupportedLicense]) -> License {
+        license.prefer(declared);
+        if !license.is_uncertain() {
+            return license;
+        }
+
+        // the file may not be a license after all, so leaving it out is the default
+        let mut options: Vec<SelectOption<License>> = vec![SelectOption {
+            name: "none of them".to_string(),
+            value: Some(License::from_name(SupportedLicense::Unknown.to_string())),
+        }];
+
+        options.extend(
+            license
+                .alternatives(3)
+                .into_iter()
+                .map(|(alternative, coverage)| SelectOption {
+                    name: format!("{:.0}% of its text found", coverage * 100.0),
+                    value: Some(alternative),
+                }),
+        );
+
+        let label = format!(
+            "The license in {} doesn't match exactly one license I know, which one is it?",
+            license.path.clone().unwrap_or_default()
+        );
+
+        self.merge_field("license", options, Some(label))
+            .unwrap_or(license)
+    }
+
+    fn merge_licenses(&self, converted_configs: Vec<ConverterOutput>) -> Option<License> {
+        let selected: Option<License>;
+
+        // the licenses declared by name, like in the manifests, settle the license files matching more than one license
+        let declared: Vec<SupportedLicense> = converted_configs
+            .iter()
+            .filter_map(|config| config.license.as_ref())
+            .filter(|license| license.path.is_none())
+            .flat_map(|license| match &license.expression {
+                Some(expression) => expression.licenses().iter().map(|l| l.name).collect(),
+                None => vec![license.name],
+            })
+            .collect();
+
+        // the license files differing too much from the text of any license are confirmed by the user
+        let converted_configs: Vec<ConverterOutput> = converted_configs
+            .into_iter()
+            .map(|mut config| {
+                config.license = config
+                    .license
+                    .map(|license| self.confirm_license(license, &declared));
+                config
+            })
+            .collect();
 
         // check if thereisn't any license
         if converted_configs.iter().all(|config| {
@@ -166,16 +399,16 @@ impl Merger {
                     value: Some(License::from_name(license.to_string())),
                 })
                 .collect();
-            
+
             // make choese between them
             selected = self.merge_field(
-                "license", 
-                available, 
+                "license",
+                available,
                 Some("Oops! It seems I couldn't find a license for your project. Choose one from the list:".to_string())
             );
 
             return selected;
-        } 
+        }
         // values are the actual licenses, so the value that we see in the merger
         // are the Display trait of the license
         selected = self.merge_field(
@@ -186,7 +419,9 @@ impl Merger {
                     config.license.is_some()
                         && config.license.as_ref().unwrap().name != SupportedLicense::Unknown
                 })
-                .unique_by(|item| item.license.as_ref().unwrap().name)
+                // the licenses read from files come first so that the links to the files are kept
+                .sorted_by_key(|item| !item.license.as_ref().unwrap().has_file())
+                .unique_by(|item| item.license.as_ref().unwrap().key())
                 .map(|config| SelectOption {
                     name: config.source_config_file_path.clone(),
                     value: Some(config.license.clone().unwrap()),
@@ -197,4 +432,165 @@ impl Merger {
 
         selected
     }
+
+    /// Merges the Dockerfiles and docker-compose files found in the project into a single docker object
+    ///
+    /// If there are multiple Dockerfiles or compose files, the user will be asked which one describes the project
+    fn merge_docker(&self, converted_configs: Vec<ConverterOutput>) -> Option<Docker> {
+        let dockers: Vec<Docker> = converted_configs
+            .iter()
+            .filter_map(|config| config.docker.clone())
+            .collect();
+
+        if dockers.is_empty() {
+            return None;
+        }
+
+        let mut docker = Docker::empty();
+
+        docker.image = self.merge_field(
+            "dockerfile",
+            dockers
+                .iter()
+                .filter(|d| d.image.is_some())
+                .map(|d| SelectOption {
+                    value: d.image.clone(),
+                    name: d.image.as_ref().unwrap().path.clone(),
+                })
+                .collect(),
+            None,
+        );
+
+        let compose = self.merge_field(
+            "docker-compose",
+            dockers
+                .iter()
+                .filter(|d| d.compose_file.is_some())
+                .map(|d| SelectOption {
+                    value: Some(d.clone()),
+                    name: d.compose_file.clone().unwrap(),
+                })
+                .collect(),
+            None,
+        );
+
+        if let Some(compose) = compose {
+            docker.compose_file = compose.compose_file;
+            docker.services = compose.services;
+        }
+
+        Some(docker)
+    }
+
+    /// Merges the scripts of the project keeping only the ones declared in the files closest to the project root,
+    /// the nested ones usually belong to sub packages or vendored code
+    fn merge_scripts(&self, scripts: Vec<Script>) -> Option<Vec<Script>> {
+        let depth = |script: &Script| Path::new(&script.source).components().count();
+        let min_depth = scripts.iter().map(depth).min()?;
+
+        Some(
+            scripts
+                .into_iter()
+                .filter(|script| depth(script) == min_depth)
+                .unique_by(|script| script.command.clone())
+                .collect(),
+        )
+    }
+
+    /// Merges the env templates of the project keeping only the ones closest to the project root, the nested ones
+    /// usually configure sub packages
+    fn merge_env_vars(&self, converted_configs: Vec<ConverterOutput>) -> Option<Vec<EnvVar>> {
+        let env_vars: Vec<EnvVar> = converted_configs
+            .iter()
+            .flat_map(|config| config.env_vars.clone())
+            .flatten()
+            .collect();
+
+        let depth = |env_var: &EnvVar| Path::new(&env_var.source).components().count();
+        let min_depth = env_vars.iter().map(depth).min()?;
+
+        Some(
+            env_vars
+                .into_iter()
+                .filter(|env_var| depth(env_var) == min_depth)
+                .unique_by(|env_var| env_var.name.clone())
+                .collect(),
+        )
+    }
+
+    /// Merges the prerequisites of the project into a single version per tool
+    ///
+    /// The pin files win over the ranges of the manifests as they hold the version actually used, if there are
+    /// still multiple versions of a tool the user will be asked which one to keep
+    fn merge_prerequisites(
+        &self,
+        converted_configs: Vec<ConverterOutput>,
+    ) -> Option<Vec<Prerequisite>> {
+        let prerequisites: Vec<Prerequisite> = converted_configs
+            .iter()
+            .flat_map(|config| config.prerequisites.clone())
+            .flatten()
+            .collect();
+
+        let depth =
+            |prerequisite: &Prerequisite| Path::new(&prerequisite.source).components().count();
+
+        let tools: Vec<String> = prerequisites
+            .iter()
+            .map(|prerequisite| prerequisite.tool.clone())
+            .unique()
+            .collect();
+
+        let merged: Vec<Prerequisite> = tools
+            .iter()
+            .filter_map(|tool| {
+                let candidates: Vec<&Prerequisite> = prerequisites
+                    .iter()
+                    .filter(|prerequisite| &prerequisite.tool == tool)
+                    .collect();
+
+                let merge = |kept: &dyn Fn(&Prerequisite) -> bool| -> Option<Prerequisite> {
+                    let candidates: Vec<&&Prerequisite> = candidates
+                        .iter()
+                        .filter(|prerequisite| kept(prerequisite))
+                        .collect();
+
+                    let min_depth = candidates.iter().map(|p| depth(p)).min()?;
+
+                    self.merge_field(
+                        tool,
+                        candidates
+                            .into_iter()
+                            .filter(|prerequisite| depth(prerequisite) == min_depth)
+                            .unique_by(|prerequisite| prerequisite.version.clone())
+                            .map(|prerequisite| SelectOption {
+                                value: Some((*prerequisite).clone()),
+                                name: prerequisite.source.clone(),
+                            })
+                            .collect(),
+                        None,
+                    )
+                };
+
+                // the pin files tell the version to install, the oldest supported version declared by the
+                // manifests is kept next to it
+                match merge(&|prerequisite| prerequisite.pinned) {
+                    Some(pinned) => match merge(&|p| !p.pinned && p.minimum) {
+                        Some(minimum) => Some(Prerequisite {
+                            pin: Some(pinned.version),
+                            ..minimum
+                        }),
+                        None => Some(pinned),
+                    },
+                    None => merge(&|prerequisite| !prerequisite.pinned),
+                }
+            })
+            .collect();
+
+        if merged.is_empty() {
+            return None;
+        }
+
+        Some(merged)
+    }
 }
@@ -1,5 +1,6 @@
 use crate::{
     converter::Dependencies,
+    elements::{lockfile::LockedPackage, script::Script},
     utils::{paths, Tech},
 };
 use anyhow::{anyhow, Error};
@@ -78,7 +79,11 @@ pub fn scan_techs(paths: &Vec<String>) -> Result<Vec<String>, Error> {
 }
 
 /// Returns the list of dependencies present in the project found through the dependencies field in the configs files
-pub fn scan_dependencies(dependencies: Dependencies) -> Result<Vec<String>, Error> {
+/// and the packages pinned by the lockfiles, so a framework pulled in transitively is detected too
+pub fn scan_dependencies(
+    dependencies: Dependencies,
+    locked_packages: &[LockedPackage],
+) -> Result<Vec<String>, Error> {
     let contents: String = paths::read_util_file_contents(paths::UtilityPath::Techs);
     let all_techs: HashMap<String, Tech> = match serde_yaml::from_str(&contents) {
         Ok(all_techs) => all_techs,
@@ -92,7 +97,7 @@ pub fn scan_dependencies(dependencies: Dependencies) -> Result<Vec<String>, Erro
         if index > 40 {
             break;
         }
-        let regex_set = match regex::RegexSet::new(tech.dependency_names) {
+        let regex_set = match regex::RegexSet::new(&tech.dependency_names) {
             Ok(regex_set) => regex_set,
             Err(_) => return Err(anyhow!("Error creating regex set")),
         };
@@ -104,11 +109,122 @@ pub fn scan_dependencies(dependencies: Dependencies) -> Result<Vec<String>, Erro
                 .collect();
 
             if !matches.is_empty() {
-                dependencies_present.push(name);
+                dependencies_present.push(name.clone());
                 break;
             }
         }
+
+        if dependencies_present.contains(&name) {
+            continue;
+        }
+
+        // the lockfiles list every package installed, match the exact name only to not pick up every plugin
+        // and helper package of an ecosystem (eg. `react-is` is not React)
+        let locked = locked_packages.iter().any(|package| {
+            tech.dependency_names
+                .iter()
+                .any(|dependency_name| dependency_name == &package.name)
+        });
+
+        if locked {
+            dependencies_present.push(name);
+        }
     }
 
     Ok(dependencies_present)
 }
+
+/// Returns the commands running the tests of the given techs, the frameworks already run by a manifest script
+/// (eg. `"test": "jest --coverage"`) are left out as the script is the intended way to run them
+pub fn scan_test_commands(
+    techs: &[String],
+    test_commands: &[Script],
+) -> Result<Vec<Script>, Error> {
+    let contents: String = paths::read_util_file_contents(paths::UtilityPath::Techs);
+    let all_techs: HashMap<String, Tech> = match serde_yaml::from_str(&contents) {
+        Ok(all_techs) => all_techs,
+        Err(_) => return Err(anyhow!("Error parsing techs file")),
+    };
+
+    let mut commands: Vec<Script> = all_techs
+        .into_iter()
+        .filter(|(name, _)| techs.contains(name))
+        .filter(|(name, tech)| {
+            !test_commands
+                .iter()
+                .any(|script| runs_tech(script, name, tech.test_command.as_deref()))
+        })
+        .filter_map(|(name, tech)| {
+            Some(Script {
+                command: tech.test_command?,
+                name,
+                description: None,
+                source: String::new(),
+            })
+        })
+        .collect();
+
+    commands.sort_by(|a, b| a.name.cmp(&b.name));
+
+    Ok(commands)
+}
+
+/// Returns whether the script runs the tests of the tech, either with the command of the tech or by calling the tech
+/// by name (eg. `jest --coverage` runs jest, `vitest` and `cargo test` don't run jest and go)
+fn runs_tech(script: &Script, name: &str, test_command: Option<&str>) -> bool {
+    let calls_tech = |text: &str| {
+        text.split(|c: char| c.is_whitespace() || "/`;&|()\"'".contains(c))
+            .any(|word| word == name)
+    };
+
+    test_command.is_some_and(|command| script.command.contains(command))
+        || calls_tech(&script.command)
+        || script.description.as_deref().is_some_and(calls_tech)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn script(command: &str, description: Option<&str>) -> Script {
+        Script {
+            name: "test".to_string(),
+            command: command.to_string(),
+            description: description.map(|d| d.to_string()),
+            source: String::new(),
+        }
+    }
+
+    #[test]
+    fn finds_the_tech_called_by_a_script() {
+        let jest = script("npm run test", Some("`jest --coverage`"));
+        let pest = script("vendor/bin/pest --parallel", None);
+
+        assert!(runs_tech(&jest, "jest", Some("npx jest")));
+        assert!(runs_tech(&pest, "pest", Some("vendor/bin/pest")));
+        assert!(runs_tech(
+            &script("go test ./...", None),
+            "go",
+            Some("go test ./...")
+        ));
+    }
+
+    #[test]
+    fn ignores_the_tech_names_inside_other_words() {
+        assert!(!runs_tech(
+            &script("cargo test", None),
+            "go",
+            Some("go test ./...")
+        ));
+        assert!(!runs_tech(
+            &script("npm run test", Some("`vitest`")),
+            "jest",
+            Some("npx jest")
+        ));
+        assert!(!runs_tech(
+            &script("python manage.py test", Some("django")),
+            "go",
+            None
+        ));
+    }
+}
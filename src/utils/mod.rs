@@ -10,6 +10,7 @@ use std::collections::HashMap;
 pub mod outputs {
     pub const README: &str = "README.md";
     pub const CONTRIBUTING: &str = "CONTRIBUTING.md";
+    pub const CHANGELOG: &str = "CHANGELOG.md";
 }
 
 /// Paths to significant files
@@ -18,20 +19,48 @@ pub mod paths {
         Configs,
         Techs,
         Lincenses,
+        Languages,
 
         // license templates
         Apache20,
         MIT,
-        GNUGPL,
+        EUPL12,
+        MPL20,
+        BSD2Clause,
+        BSD3Clause,
+        ZeroBSD,
+        Isc,
+        AGPL30,
+        LGPL21,
+        LGPL30,
+        GPL20,
+        GPL30,
+        Unlicense,
+        Zlib,
+        BSL10,
         CreativeCommonsAttributionShareAlike40,
 
         //README.md templates
         // small pieces of markdown which require some data to be filled in
         ShieldReadme,
         AuthorReadme,
+        AuthorsReadme,
+        AvatarsReadme,
         ContribRocksReadme,
         SupportReadme,
         LicenseReadme,
+        DockerReadme,
+        UsageReadme,
+        FeaturesReadme,
+        InstallReadme,
+        DependenciesReadme,
+        PrerequisitesReadme,
+        EnvReadme,
+        TestsReadme,
+        BadgeReadme,
+        CiReadme,
+        LanguagesReadme,
+        StructureReadme,
 
         // large macro templates of the README file
         HeaderReadme,
@@ -41,6 +70,10 @@ pub mod paths {
 
         //CONTRIBUTING.md templates
         BodyContributing,
+
+        //CHANGELOG.md templates
+        HeaderChangelog,
+        ReleaseChangelog,
     }
 
     /// Returns the path of the given file for the given utility type
@@ -49,14 +82,30 @@ pub mod paths {
             UtilityPath::Configs => include_str!("../../conf/configs.yml"),
             UtilityPath::Techs => include_str!("../../conf/techs.yml"),
             UtilityPath::Lincenses => include_str!("../../conf/licenses.yml"),
+            UtilityPath::Languages => include_str!("../../conf/languages.yml"),
             UtilityPath::Apache20 => include_str!("../../conf/lic/APACHE_20.md"),
             UtilityPath::MIT => include_str!("../../conf/lic/MIT.md"),
-            UtilityPath::GNUGPL => include_str!("../../conf/lic/GNU_GPL.md"),
+            UtilityPath::EUPL12 => include_str!("../../conf/lic/EUPL_12.md"),
+            UtilityPath::MPL20 => include_str!("../../conf/lic/MPL_20.md"),
+            UtilityPath::BSD2Clause => include_str!("../../conf/lic/BSD_2_CLAUSE.md"),
+            UtilityPath::BSD3Clause => include_str!("../../conf/lic/BSD_3_CLAUSE.md"),
+            UtilityPath::ZeroBSD => include_str!("../../conf/lic/ZERO_BSD.md"),
+            UtilityPath::Isc => include_str!("../../conf/lic/ISC.md"),
+            UtilityPath::AGPL30 => include_str!("../../conf/lic/AGPL_30.md"),
+            UtilityPath::LGPL21 => include_str!("../../conf/lic/LGPL_21.md"),
+            UtilityPath::LGPL30 => include_str!("../../conf/lic/LGPL_30.md"),
+            UtilityPath::GPL20 => include_str!("../../conf/lic/GPL_20.md"),
+            UtilityPath::GPL30 => include_str!("../../conf/lic/GPL_30.md"),
+            UtilityPath::Unlicense => include_str!("../../conf/lic/UNLICENSE.md"),
+            UtilityPath::Zlib => include_str!("../../conf/lic/ZLIB.md"),
+            UtilityPath::BSL10 => include_str!("../../conf/lic/BSL_10.md"),
             UtilityPath::CreativeCommonsAttributionShareAlike40 => {
                 include_str!("../../conf/lic/CREATIVE_COMMONS_ATTRIBUTION_SHARE_ALIKE_40.md")
             }
             UtilityPath::ShieldReadme => include_str!("../../conf/tpl/readme/SHIELD.md"),
             UtilityPath::AuthorReadme => include_str!("../../conf/tpl/readme/AUTHOR.md"),
+            UtilityPath::AuthorsReadme => include_str!("../../conf/tpl/readme/AUTHORS.md"),
+            UtilityPath::AvatarsReadme => include_str!("../../conf/tpl/readme/AVATARS.md"),
             UtilityPath::ContribRocksReadme => {
                 include_str!("../../conf/tpl/readme/CONTRIB_ROCKS.md")
             }
@@ -66,7 +115,25 @@ pub mod paths {
             UtilityPath::BodyReadme => include_str!("../../conf/tpl/readme/BODY.md"),
             UtilityPath::FooterReadme => include_str!("../../conf/tpl/readme/FOOTER.md"),
             UtilityPath::LicenseReadme => include_str!("../../conf/tpl/readme/LICENSE.md"),
+            UtilityPath::DockerReadme => include_str!("../../conf/tpl/readme/DOCKER.md"),
+            UtilityPath::UsageReadme => include_str!("../../conf/tpl/readme/USAGE.md"),
+            UtilityPath::FeaturesReadme => include_str!("../../conf/tpl/readme/FEATURES.md"),
+            UtilityPath::InstallReadme => include_str!("../../conf/tpl/readme/INSTALL.md"),
+            UtilityPath::DependenciesReadme => {
+                include_str!("../../conf/tpl/readme/DEPENDENCIES.md")
+            }
+            UtilityPath::PrerequisitesReadme => {
+                include_str!("../../conf/tpl/readme/PREREQUISITES.md")
+            }
+            UtilityPath::EnvReadme => include_str!("../../conf/tpl/readme/ENV.md"),
+            UtilityPath::TestsReadme => include_str!("../../conf/tpl/readme/TESTS.md"),
+            UtilityPath::BadgeReadme => include_str!("../../conf/tpl/readme/BADGE.md"),
+            UtilityPath::CiReadme => include_str!("../../conf/tpl/readme/CI.md"),
+            UtilityPath::LanguagesReadme => include_str!("../../conf/tpl/readme/LANGUAGES.md"),
+            UtilityPath::StructureReadme => include_str!("../../conf/tpl/readme/STRUCTURE.md"),
             UtilityPath::BodyContributing => include_str!("../../conf/tpl/contributing/BODY.md"),
+            UtilityPath::HeaderChangelog => include_str!("../../conf/tpl/changelog/HEADER.md"),
+            UtilityPath::ReleaseChangelog => include_str!("../../conf/tpl/changelog/RELEASE.md"),
         };
 
         target.to_string()
@@ -99,9 +166,52 @@ pub struct Shield {
 pub struct Tech {
     pub config_files: Vec<String>,
     pub dependency_names: Vec<String>,
+    /// The command running the tests written with the tech, only for test frameworks and languages
+    #[serde(default)]
+    pub test_command: Option<String>,
     pub shield: Shield,
 }
 
+impl Shield {
+    /// Creates a shield not listed in the techs file, label and message are escaped following the shields.io
+    /// static badge rules
+    ///
+    /// Reference: https://shields.io/badges/static-badge
+    pub fn new(
+        label: &str,
+        message: &str,
+        color: &str,
+        logo: &str,
+        alt_text: &str,
+        target: &str,
+    ) -> Self {
+        let escape = |s: &str| {
+            s.replace('%', "%25")
+                .replace('-', "--")
+                .replace('_', "__")
+                .replace(' ', "%20")
+                // the characters ending the path of the badge url or splitting it, e.g. C# or TCP/IP
+                .replace('#', "%23")
+                .replace('?', "%3F")
+                .replace('/', "%2F")
+                .replace('&', "%26")
+        };
+
+        Shield {
+            label: escape(label),
+            message: escape(message),
+            color: color.to_string(),
+            logo: logo.to_string(),
+            label_color: String::new(),
+            logo_color: "white".to_string(),
+            style: "flat-square".to_string(),
+            logo_width: 20,
+            alt_text: alt_text.to_string(),
+            target: target.to_string(),
+        }
+    }
+}
+
 impl GenMarkdown for Shield {
     fn gen_md(&self) -> Result<String, Error> {
         let shield_tpl = paths::read_util_file_contents(paths::UtilityPath::ShieldReadme);